@@ -1,120 +1,142 @@
 use crate::achievements::{
-    Achievement, AchievementLanguage, AchievementPresention, PresentationType, ProgrammingLanguage,
-    SerializableAchievement, Sprint,
+    Achievement, AchievementError, AchievementLanguage, AchievementPresention, Sprint,
 };
-use crate::default_values;
+use crate::course::{self, Course};
 
-use chrono::{DateTime, Local};
+use std::path::Path;
 
-use csv;
+pub const ACHIEVEMENTS_FILE: &str = "achievements.csv";
 
-pub fn read_achievements_from_gui() -> Result<Vec<Achievement>, csv::Error> {
-    let mut rdr = csv::Reader::from_path("achievements.csv")?;
-    let mut serialized_achievements = Vec::new();
-    for result in rdr.deserialize() {
-        let record: SerializableAchievement = result?;
-        serialized_achievements.push(record.clone());
-    }
+/// The columns of achievements.csv.
+/// Files written by this app and the Google Sheets export share the same layout.
+pub const COLUMNS: [&str; 11] = [
+    "id",
+    "link",
+    "title",
+    "deadline",
+    "done",
+    "present_soon",
+    "grade",
+    "presenting_type",
+    "programming_language",
+    "sprint",
+    "comment",
+];
+
+/// A row that could not be parsed.
+/// The record is kept as it was read so it can be fixed, skipped or replaced by its default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrokenRow {
+    pub row: usize,
+    /// Where among the parsed achievements the row was found.
+    pub position: usize,
+    pub record: Vec<String>,
+    pub errors: Vec<AchievementError>,
+}
+
+pub struct AchievementFile {
+    pub achievements: Vec<Achievement>,
+    pub broken_rows: Vec<BrokenRow>,
+}
+
+pub fn read_achievements(path: &Path) -> Result<AchievementFile, csv::Error> {
+    let rdr = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path.join(ACHIEVEMENTS_FILE))?;
+    read_achievements_from_reader(rdr)
+}
+
+/// Parses the contents of an achievements file, e.g. as stored in a git commit.
+pub fn read_achievements_from_str(contents: &str) -> Result<AchievementFile, csv::Error> {
+    let rdr = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(contents.as_bytes());
+    read_achievements_from_reader(rdr)
+}
+
+pub fn read_achievements_from_reader<R: std::io::Read>(
+    mut rdr: csv::Reader<R>,
+) -> Result<AchievementFile, csv::Error> {
     let mut achievements = Vec::new();
-    for serialized_achievement in serialized_achievements {
-        achievements.push(Achievement {
-            id: serialized_achievement.id,
-            title: serialized_achievement.title,
-            deadline: if serialized_achievement.deadline == None {
-                None
-            } else {
-                Some(
-                    DateTime::parse_from_str(
-                        &format!("{} 0:0:0 +0000", serialized_achievement.deadline.unwrap()),
-                        "%b %d, %Y %H:%M:%S %z",
-                    )
-                    .unwrap()
-                    .with_timezone(&Local),
-                )
-            },
-            done: serialized_achievement.done,
-            present_soon: serialized_achievement.present_soon,
-            grade: serialized_achievement.grade,
-            presenting_type: AchievementPresention::from_string(
-                serialized_achievement.presenting_type,
-            ),
-            programming_language: AchievementLanguage::from_string(
-                serialized_achievement.programming_language,
-            ),
-            sprint: serialized_achievement.sprint,
-            comment: serialized_achievement.comment,
-        });
+    let mut broken_rows = Vec::new();
+
+    for (i, result) in rdr.records().enumerate() {
+        let record = result?;
+        let row = record
+            .position()
+            .map(|position| position.line() as usize)
+            .unwrap_or(i + 2);
+        let record = record.iter().map(String::from).collect::<Vec<String>>();
+
+        match parse_record(row, &record) {
+            Ok(achievement) => achievements.push(achievement),
+            Err(errors) => broken_rows.push(BrokenRow {
+                row,
+                position: achievements.len(),
+                record,
+                errors,
+            }),
+        }
     }
-    Ok(achievements)
+
+    Ok(AchievementFile {
+        achievements,
+        broken_rows,
+    })
 }
 
-pub fn read_achievements_from_google_sheets() -> Result<Vec<Achievement>, csv::Error> {
-    let mut rdr = csv::Reader::from_path("achievements.csv")?;
-    let mut achievements = Vec::new();
+/// Parses one row of achievements.csv, collecting every invalid cell instead of stopping at the first.
+pub fn parse_record(row: usize, record: &[String]) -> Result<Achievement, Vec<AchievementError>> {
+    let mut errors = Vec::new();
+    let field = |column: usize| record.get(column).map(String::as_str).unwrap_or_default();
+    let mut error = |column: usize| {
+        errors.push(AchievementError {
+            row,
+            column: COLUMNS[column],
+            value: field(column).to_string(),
+        })
+    };
 
-    for result in rdr.records() {
-        let result = result.unwrap();
-        let id = result[0].to_string();
-        let title = result[2].to_string();
-        let deadline = match &result[3] {
-            "" => None,
-            _ => Some(
-                DateTime::parse_from_str(
-                    &format!("{} 0:0:0 +0000", &result[3]),
-                    "%b %d, %Y %H:%M:%S %z",
-                )
-                .unwrap()
-                .with_timezone(&Local),
-            ),
-        };
-        let done = &result[4] == "TRUE";
-        let present_soon = &result[5] == "TRUE";
-        let grade = result[6].parse().expect("Grade is not a number!");
-        let presenting_type = match &result[7] {
-            "Lab" => AchievementPresention::Single(PresentationType::Lab),
-            "Studium" => AchievementPresention::Single(PresentationType::Studium),
-            "Special" => AchievementPresention::Single(PresentationType::Special),
-            "Lab, Studium" => AchievementPresention::Either {
-                first: PresentationType::Lab,
-                second: PresentationType::Studium,
-            },
-            "Report" => AchievementPresention::Single(PresentationType::Report),
-            _ => panic!("Unknown presentation type"),
-        };
-        let programming_language = match &result[8] {
-            "C" => AchievementLanguage::Single(ProgrammingLanguage::C),
-            "Java" => AchievementLanguage::Single(ProgrammingLanguage::Java),
-            "Terminal" => AchievementLanguage::Single(ProgrammingLanguage::Bash),
-            "Git" => AchievementLanguage::Single(ProgrammingLanguage::Git),
-            "Essä" => AchievementLanguage::Single(ProgrammingLanguage::NoLanguage),
-            "Möte" => AchievementLanguage::Single(ProgrammingLanguage::NoLanguage),
-            "C&Java" => AchievementLanguage::Both {
-                first: ProgrammingLanguage::C,
-                second: ProgrammingLanguage::Java,
-            },
-
-            "C/Java" => AchievementLanguage::Either {
-                first: ProgrammingLanguage::C,
-                second: ProgrammingLanguage::Java,
-            },
-            e => panic!("Unknown programming language {e}"),
-        };
-        let sprint = match &result[9] {
-            "Sprint 1" => Sprint::Sprint1,
-            "Sprint 2" => Sprint::Sprint2,
-            "Sprint 3" => Sprint::Sprint3,
-            "Sprint 4" => Sprint::Sprint4,
-            "Project" => Sprint::Project,
-            "Projekt" => Sprint::Project,
-            "IDK" => Sprint::Unclear,
-            e => panic!("Unknown sprint {e}"),
-        };
-        let comment = match &result[10] {
-            "" => None,
-            _ => Some(result[10].to_string()),
-        };
+    let id = field(0).to_string();
+    if id.is_empty() {
+        error(0);
+    }
+    let title = field(2).to_string();
+    let deadline = match field(3) {
+        "" => None,
+        deadline => course::parse_date(deadline).map_err(|_| error(3)).ok(),
+    };
+    let done = parse_bool(field(4)).ok_or_else(|| error(4)).ok();
+    let present_soon = parse_bool(field(5)).ok_or_else(|| error(5)).ok();
+    let grade = field(6).trim().parse::<i8>().map_err(|_| error(6)).ok();
+    let presenting_type = AchievementPresention::from_string(field(7))
+        .ok_or_else(|| error(7))
+        .ok();
+    let programming_language = AchievementLanguage::from_string(field(8))
+        .ok_or_else(|| error(8))
+        .ok();
+    let sprint = Sprint::from_string(field(9)).ok_or_else(|| error(9)).ok();
+    let comment = match field(10) {
+        "" => None,
+        comment => Some(comment.to_string()),
+    };
 
-        achievements.push(Achievement {
+    match (
+        done,
+        present_soon,
+        grade,
+        presenting_type,
+        programming_language,
+        sprint,
+    ) {
+        (
+            Some(done),
+            Some(present_soon),
+            Some(grade),
+            Some(presenting_type),
+            Some(programming_language),
+            Some(sprint),
+        ) if errors.is_empty() => Ok(Achievement {
             id,
             title,
             deadline,
@@ -125,65 +147,145 @@ pub fn read_achievements_from_google_sheets() -> Result<Vec<Achievement>, csv::E
             programming_language,
             sprint,
             comment,
-        });
+        }),
+        _ => Err(errors),
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") || value.is_empty() {
+        Some(false)
+    } else {
+        None
     }
+}
 
-    Ok(achievements)
+pub fn read_defaults(course: &Course) -> Vec<Achievement> {
+    course.achievements.clone()
 }
 
-pub fn read_defaults() -> Vec<Achievement> {
-    let mut achievements = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: [&str; 11] = [
+        "A1",
+        "https://example.com/A1",
+        "Title",
+        "Sep 5, 2024",
+        "TRUE",
+        "false",
+        "3",
+        "Lab",
+        "C",
+        "Sprint1",
+        "",
+    ];
+
+    /// A valid record with the cell in `column` replaced by `value`.
+    fn record(column: &str, value: &str) -> Vec<String> {
+        let mut record = VALID.map(String::from).to_vec();
+        let index = COLUMNS.iter().position(|c| *c == column).unwrap();
+        record[index] = value.to_string();
+        record
+    }
+
+    fn error(row: usize, column: &'static str, value: &str) -> AchievementError {
+        AchievementError {
+            row,
+            column,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn booleans_ignore_case_and_default_to_false() {
+        for (value, expected) in [
+            ("TRUE", true),
+            ("true", true),
+            ("True", true),
+            ("FALSE", false),
+            ("false", false),
+            ("", false),
+        ] {
+            let achievement = parse_record(2, &record("done", value)).unwrap();
+            assert_eq!(achievement.done, expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn invalid_booleans_are_reported() {
+        assert_eq!(
+            parse_record(4, &record("done", "yes")).unwrap_err(),
+            vec![error(4, "done", "yes")]
+        );
+        assert_eq!(
+            parse_record(5, &record("present_soon", "1")).unwrap_err(),
+            vec![error(5, "present_soon", "1")]
+        );
+    }
+
+    #[test]
+    fn unknown_presenting_types_and_languages_are_reported() {
+        assert_eq!(
+            parse_record(2, &record("presenting_type", "Lecture")).unwrap_err(),
+            vec![error(2, "presenting_type", "Lecture")]
+        );
+        assert_eq!(
+            parse_record(3, &record("programming_language", "Rust")).unwrap_err(),
+            vec![error(3, "programming_language", "Rust")]
+        );
+    }
+
+    #[test]
+    fn bad_dates_are_reported() {
+        assert_eq!(
+            parse_record(7, &record("deadline", "Sep 45, 2024")).unwrap_err(),
+            vec![error(7, "deadline", "Sep 45, 2024")]
+        );
+    }
+
+    #[test]
+    fn short_rows_report_every_missing_cell() {
+        let record = VALID[..6]
+            .iter()
+            .map(|cell| cell.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            parse_record(9, &record).unwrap_err(),
+            vec![
+                error(9, "grade", ""),
+                error(9, "presenting_type", ""),
+                error(9, "programming_language", ""),
+                error(9, "sprint", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn broken_rows_keep_their_line_and_position() {
+        // The deadline holds a comma, so every cell is quoted.
+        let line = |record: &[String]| {
+            record
+                .iter()
+                .map(|cell| format!("\"{}\"", cell))
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        let valid = line(&record("comment", ""));
+        let broken = line(&record("grade", "five"));
+        let contents = format!("{}\n{}\n{}\n", COLUMNS.join(","), valid, broken);
 
-    let ids = default_values::IDS;
-    let titles = default_values::TITLES;
-    let deadlines = default_values::DEADLINES;
-    let done = default_values::DONE;
-    let present_soon = default_values::PRESENT_SOON;
-    let grades = default_values::GRADES;
-    let presenting_types = default_values::PRESENTING_TYPES;
-    let programming_languages = default_values::PROGRAMMING_LANGUAGES;
-    let sprints = default_values::SPRINTS;
-    let comments = default_values::COMMENTS;
-
-    for i in 0..ids.len() {
-        achievements.push(Achievement {
-            id: ids[i].to_string(),
-            title: titles[i].to_string(),
-            deadline: if deadlines[i] == "" {
-                None
-            } else {
-                Some(
-                    DateTime::parse_from_str(
-                        &format!("{} 0:0:0 +0000", deadlines[i]),
-                        "%b %d, %Y %H:%M:%S %z",
-                    )
-                    .unwrap()
-                    .with_timezone(&Local),
-                )
-            },
-            done: done[i],
-            present_soon: present_soon[i],
-            grade: grades[i],
-            presenting_type: AchievementPresention::from_string(presenting_types[i].to_string()),
-            programming_language: AchievementLanguage::from_string(
-                programming_languages[i].to_string(),
-            ),
-            sprint: match sprints[i] {
-                "Sprint 1" => Sprint::Sprint1,
-                "Sprint 2" => Sprint::Sprint2,
-                "Sprint 3" => Sprint::Sprint3,
-                "Sprint 4" => Sprint::Sprint4,
-                "Project" => Sprint::Project,
-                "Projekt" => Sprint::Project,
-                "IDK" => Sprint::Unclear,
-                e => panic!("Unknown sprint {e}"),
-            },
-            comment: if comments[i] == "" {
-                None
-            } else {
-                Some(comments[i].to_string())
-            },
-        });
+        let rdr = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(contents.as_bytes());
+        let file = read_achievements_from_reader(rdr).unwrap();
+        assert_eq!(file.achievements.len(), 1);
+        assert_eq!(file.broken_rows.len(), 1);
+        assert_eq!(file.broken_rows[0].row, 3);
+        assert_eq!(file.broken_rows[0].position, 1);
+        assert_eq!(file.broken_rows[0].errors, vec![error(3, "grade", "five")]);
     }
-    achievements
 }
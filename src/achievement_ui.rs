@@ -1,8 +1,49 @@
-use crate::achievements::*;
 use crate::application::{Application, Fieled, Filter, FilterType};
+use achievements_enhanced::achievements::*;
+use achievements_enhanced::course;
+use chrono::Local;
 use eframe::egui;
 
+/// A combo box offering every value in `options`, with the one picked this frame if it differs.
+fn choice<T: Clone + PartialEq>(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    current: &T,
+    options: &[T],
+    text: impl Fn(&T) -> String,
+) -> (egui::Response, Option<T>) {
+    let mut picked = None;
+    let response = egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(text(current))
+        .show_ui(ui, |ui| {
+            for option in options {
+                if ui
+                    .selectable_label(option == current, text(option))
+                    .clicked()
+                    && option != current
+                {
+                    picked = Some(option.clone());
+                }
+            }
+        })
+        .response;
+    (response, picked)
+}
+
 impl Application {
+    fn language_text(&self, programming_language: &AchievementLanguage) -> String {
+        match programming_language {
+            AchievementLanguage::Single(ProgrammingLanguage::NoLanguage) => {
+                self.language.no_specific_language.clone()
+            }
+            AchievementLanguage::Single(programming_language) => {
+                format!("{:?}", programming_language)
+            }
+            AchievementLanguage::Both { first, second } => format!("{:?} & {:?}", first, second),
+            AchievementLanguage::Either { first, second } => format!("{:?} / {:?}", first, second),
+        }
+    }
+
     pub fn achievements_ui(&mut self, ctx: &egui::Context) {
         let achievements = self
             .filtered_achievements()
@@ -11,283 +52,521 @@ impl Application {
             .collect::<Vec<Achievement>>();
 
         let settings = self.settings.clone();
+        let grades = self.workspace().course.grades();
 
         egui::CentralPanel::default().show(ctx, |ui| {
             self.menu_bar(ctx, ui);
+            self.search_ui(ui);
 
             egui::ScrollArea::both()
                 .stick_to_right(true)
                 .show(ui, |ui| {
-                egui::Grid::new("Achievements Grid")
-                    .spacing(egui::vec2(
-                        self.settings.font_size * 2.0,
-                        self.settings.font_size * 2.0,
-                    ))
-                    .num_columns(12)
-                    .with_row_color(move |row_index, style| {
-                        if row_index > 0
-                            && row_index < achievements.len() + 1
-                            && achievements[row_index - 1].done
-                        {
-                            let green = match settings.dark_mode {
-                                true => egui::Color32::from_rgba_unmultiplied(0, 255, 0, 25),
-                                false => egui::Color32::from_rgba_unmultiplied(0, 255, 0, 100),
-                            };
-                            Some(green)
-                        } else if row_index > 0
-                            && row_index < achievements.len() + 1
-                            && achievements[row_index - 1].present_soon
-                        {
-                            let yellow = match settings.dark_mode {
-                                true => egui::Color32::from_rgba_unmultiplied(255, 255, 0, 25),
-                                false => egui::Color32::from_rgba_unmultiplied(255, 255, 0, 100),
-                            };
-                            Some(yellow)
-                        } else if row_index % 2 == 1 {
-                            Some(style.visuals.faint_bg_color)
-                        } else {
-                            None
-                        }
-                    })
-                    .show(ui, |ui| {
-                        let id = self.heading(ui, self.language.id.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.id.reverse()));
-                        });
-                        let title = self.heading(ui, self.language.title.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.title.reverse()));
-                        });
-                        let deadline = self.heading(ui, self.language.deadline.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.deadline.reverse()));
-                        });
-                        let done = self.heading(ui, self.language.done.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.done.reverse()));
-                        });
-                        let present_soon = self.heading(ui, self.language.present_soon.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.present_soon.reverse()));
-                        });
-                        let grade = self.heading(ui, self.language.grade.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.grade.reverse()));
-                        });
-                        let presenting_type = self.heading(ui, self.language.presenting_type.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.presenting_type.reverse()));
-                        });
-                        let programming_language = self.heading(ui, self.language.programming_language.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.programming_language.reverse()));
-                        });
-                        let sprint = self.heading(ui, self.language.sprint.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.sprint.reverse()));
-                        });
-                        let comment = self.heading(ui, self.language.comment.clone()).on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui(|ui| {
-                            ui.label(format!("{} {}", self.language.click_to_sort, self.sorting.comment.reverse()));
-                        });
-                        if id.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::ID);
-                        }
-                        if title.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::Title);
-                        }
-                        if deadline.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::Deadline);
-                        }
-                        if done.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::Done);
-                        }
-                        if present_soon.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::PresentSoon);
-                        }
-                        if grade.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::Grade);
-                        }
-                        if presenting_type.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::PresentingType);
-                        }
-                        if programming_language.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::ProgrammingLanguage);
-                        }
-                        if sprint.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::Sprint);
-                        }
-                        if comment.clicked_by(egui::PointerButton::Primary) {
-                            self.sort_achievements(Fieled::Comment);
-                        }
-                        ui.end_row();
-
-                        for (i, achievement) in self.filtered_achievements() {
-                            let id = ui.add(egui::Hyperlink::from_label_and_url(
-                                achievement.id.clone(),
-                                format!("https://uppsala.instructure.com/courses/97453/pages/achievements#{}", achievement.id[1..].to_string()),
-                            ))
+                    egui::Grid::new("Achievements Grid")
+                        .spacing(egui::vec2(
+                            self.settings.font_size * 2.0,
+                            self.settings.font_size * 2.0,
+                        ))
+                        .num_columns(12)
+                        .with_row_color(move |row_index, style| {
+                            if row_index > 0
+                                && row_index < achievements.len() + 1
+                                && achievements[row_index - 1].done
+                            {
+                                let green = match settings.dark_mode {
+                                    true => egui::Color32::from_rgba_unmultiplied(0, 255, 0, 25),
+                                    false => egui::Color32::from_rgba_unmultiplied(0, 255, 0, 100),
+                                };
+                                Some(green)
+                            } else if row_index > 0
+                                && row_index < achievements.len() + 1
+                                && achievements[row_index - 1].present_soon
+                            {
+                                let yellow = match settings.dark_mode {
+                                    true => egui::Color32::from_rgba_unmultiplied(255, 255, 0, 25),
+                                    false => {
+                                        egui::Color32::from_rgba_unmultiplied(255, 255, 0, 100)
+                                    }
+                                };
+                                Some(yellow)
+                            } else if row_index % 2 == 1 {
+                                Some(style.visuals.faint_bg_color)
+                            } else {
+                                None
+                            }
+                        })
+                        .show(ui, |ui| {
+                            let id = self
+                                .sort_heading(ui, Fieled::ID, &self.language.id)
                                 .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
-
-                            let title = ui.add(egui::Hyperlink::from_label_and_url(
-                                egui::RichText::new(format!("{}", achievement.title))
-                                    .color(
-                                        match self.settings.dark_mode {
-                                            true => egui::Color32::LIGHT_GRAY,
-                                            false => egui::Color32::DARK_GRAY,
-                                        }),
-                                format!("https://uppsala.instructure.com/courses/97453/pages/achievements#{}", achievement.id[1..].to_string()),
-                            ))
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::ID, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let title = self
+                                .sort_heading(ui, Fieled::Title, &self.language.title)
                                 .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
-
-                            let deadline = match achievement.deadline {
-                                Some(deadline) => {
-                                    ui.label(deadline.format("%a %b %d, %Y").to_string())
-                                }
-                                None => {
-                                    ui.label("")
-                                }
-                            }
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::Title, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let deadline = self
+                                .sort_heading(ui, Fieled::Deadline, &self.language.deadline)
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::Deadline, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let done = self
+                                .sort_heading(ui, Fieled::Done, &self.language.done)
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::Done, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let present_soon = self
+                                .sort_heading(ui, Fieled::PresentSoon, &self.language.present_soon)
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::PresentSoon, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let grade = self
+                                .sort_heading(ui, Fieled::Grade, &self.language.grade)
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::Grade, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let presenting_type = self
+                                .sort_heading(
+                                    ui,
+                                    Fieled::PresentingType,
+                                    &self.language.presenting_type,
+                                )
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::PresentingType, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let programming_language = self
+                                .sort_heading(
+                                    ui,
+                                    Fieled::ProgrammingLanguage,
+                                    &self.language.programming_language,
+                                )
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::ProgrammingLanguage, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let sprint = self
+                                .sort_heading(ui, Fieled::Sprint, &self.language.sprint)
                                 .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::Sprint, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            let comment = self
+                                .sort_heading(ui, Fieled::Comment, &self.language.comment)
+                                .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                .on_hover_ui(|ui| {
+                                    ui.label(format!(
+                                        "{} {}",
+                                        self.language.click_to_sort,
+                                        self.sorting.next(Fieled::Comment, false)
+                                    ));
+                                    ui.label(self.language.shift_click_to_sort_too.clone());
+                                });
+                            if id.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::ID,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if title.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::Title,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if deadline.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::Deadline,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if done.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::Done,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if present_soon.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::PresentSoon,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if grade.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::Grade,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if presenting_type.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::PresentingType,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if programming_language.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::ProgrammingLanguage,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if sprint.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::Sprint,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            if comment.clicked_by(egui::PointerButton::Primary) {
+                                self.sort_achievements(
+                                    Fieled::Comment,
+                                    ctx.input(|i| i.modifiers.shift),
+                                );
+                            }
+                            ui.end_row();
 
-                            let done = ui.centered_and_justified(|ui| {
-                                ui.checkbox(&mut self.achievements[i].done, "")
+                            for (_, achievement) in self.filtered_achievements() {
+                                let id = ui
+                                    .add(egui::Hyperlink::from_label_and_url(
+                                        achievement.id.clone(),
+                                        self.workspace().course.link(&achievement.id),
+                                    ))
                                     .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                    .on_hover_text(self.language.right_click_to_filter.clone())
-                            });
-                            let present_soon = ui.centered_and_justified(|ui| {
-                                ui.checkbox(&mut self.achievements[i].present_soon, "")
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
+
+                                let title = ui
+                                    .add(egui::Hyperlink::from_label_and_url(
+                                        egui::RichText::new(achievement.title.to_string()).color(
+                                            match self.settings.dark_mode {
+                                                true => egui::Color32::LIGHT_GRAY,
+                                                false => egui::Color32::DARK_GRAY,
+                                            },
+                                        ),
+                                        self.workspace().course.link(&achievement.id),
+                                    ))
                                     .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                    .on_hover_text(self.language.right_click_to_filter.clone())
-                            });
-                            let grade = ui.label(achievement.grade.to_string())
-                                .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
 
-                            let presenting_type = ui.label(achievement.presenting_type.to_string())
-                                .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
+                                let mut picked_deadline = None;
+                                let deadline = ui
+                                    .horizontal(|ui| match achievement.deadline {
+                                        Some(deadline) => {
+                                            let mut day = course::day(deadline);
+                                            let picker = ui.add(
+                                                egui_extras::DatePickerButton::new(&mut day)
+                                                    .id_salt(&achievement.id)
+                                                    .format("%a %b %d, %Y")
+                                                    .calendar_week(false),
+                                            );
+                                            if picker.changed() {
+                                                picked_deadline = Some(Some(course::from_day(day)));
+                                            }
+                                            if ui
+                                                .small_button("✖")
+                                                .on_hover_text(self.language.clear_deadline.clone())
+                                                .clicked()
+                                            {
+                                                picked_deadline = Some(None);
+                                            }
+                                            picker
+                                        }
+                                        None => {
+                                            let add = ui
+                                                .small_button("+")
+                                                .on_hover_text(self.language.set_deadline.clone());
+                                            if add.clicked() {
+                                                picked_deadline = Some(Some(course::from_day(
+                                                    Local::now().date_naive(),
+                                                )));
+                                            }
+                                            add
+                                        }
+                                    })
+                                    .inner
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
 
-                            let programming_language = match &achievement.programming_language {
-                                AchievementLanguage::Single(programming_language) => {
-                                    if programming_language == &ProgrammingLanguage::NoLanguage {
-                                        ui.label(self.language.no_specific_language.clone())
-                                    } else {
-                                        ui.label(format!("{:?}", programming_language))
-                                    }
-                                },
-                                AchievementLanguage::Both { first, second } => {
-                                    ui.label(format!("{:?} & {:?}", first, second))
-                                },
-                                AchievementLanguage::Either { first, second } => {
-                                    ui.label(format!("{:?} / {:?}", first, second))
-                                },
-                            }
-                                .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
+                                let mut done = achievement.done;
+                                let mut present_soon = achievement.present_soon;
+                                let done_checkbox = ui.centered_and_justified(|ui| {
+                                    ui.checkbox(&mut done, "")
+                                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                        .on_hover_text(self.language.right_click_to_filter.clone())
+                                });
+                                let present_soon_checkbox = ui.centered_and_justified(|ui| {
+                                    ui.checkbox(&mut present_soon, "")
+                                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                        .on_hover_text(self.language.right_click_to_filter.clone())
+                                });
+                                let (grade, picked_grade) = choice(
+                                    ui,
+                                    ("Grade", &achievement.id),
+                                    &achievement.grade,
+                                    &grades,
+                                    |grade| grade.to_string(),
+                                );
+                                let grade = grade
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
 
-                            let sprint = ui.label(format!("{:?}", achievement.sprint))
-                                .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
+                                let (presenting_type, picked_presenting_type) = choice(
+                                    ui,
+                                    ("Presenting Type", &achievement.id),
+                                    &achievement.presenting_type,
+                                    &AchievementPresention::ALL,
+                                    |presenting_type| presenting_type.to_string(),
+                                );
+                                let presenting_type = presenting_type
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
+
+                                let (programming_language, picked_programming_language) = choice(
+                                    ui,
+                                    ("Programming Language", &achievement.id),
+                                    &achievement.programming_language,
+                                    &AchievementLanguage::ALL,
+                                    |programming_language| self.language_text(programming_language),
+                                );
+                                let programming_language = programming_language
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
+
+                                let (sprint, picked_sprint) = choice(
+                                    ui,
+                                    ("Sprint", &achievement.id),
+                                    &achievement.sprint,
+                                    &Sprint::ALL,
+                                    |sprint| format!("{:?}", sprint),
+                                );
+                                let sprint = sprint
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
 
-                            let comment = match &achievement.comment {
-                                Some(comment) => {
-                                    ui.label(comment)
+                                // Text being typed is kept in egui's memory until the field loses focus.
+                                let comment_id =
+                                    ui.make_persistent_id(("Comment", &achievement.id));
+                                let mut text = ui
+                                    .data_mut(|data| data.get_temp::<String>(comment_id))
+                                    .unwrap_or_else(|| {
+                                        achievement.comment.clone().unwrap_or_default()
+                                    });
+                                let comment = ui
+                                    .add(
+                                        egui::TextEdit::multiline(&mut text)
+                                            .id(comment_id)
+                                            .desired_rows(1)
+                                            .desired_width(self.settings.font_size * 12.0),
+                                    )
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(self.language.right_click_to_filter.clone());
+                                let mut picked_comment = None;
+                                if comment.lost_focus() {
+                                    let edited = Some(text.trim().to_string())
+                                        .filter(|comment| !comment.is_empty());
+                                    if edited != achievement.comment {
+                                        picked_comment = Some(edited);
+                                    }
                                 }
-                                None => {
-                                    ui.label("")
+                                if comment.has_focus() {
+                                    ui.data_mut(|data| data.insert_temp(comment_id, text));
+                                } else {
+                                    ui.data_mut(|data| data.remove::<String>(comment_id));
                                 }
-                            }
-                                .on_hover_cursor(egui::CursorIcon::PointingHand)
-                                .on_hover_text(self.language.right_click_to_filter.clone());
-                            ui.allocate_space(egui::vec2(10.0, 0.0));
+                                ui.allocate_space(egui::vec2(10.0, 0.0));
 
-                            if id.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.id = Filter::new();
-                                    self.filters.id.typ = FilterType::Include;
-                                } else {
-                                    self.filters.id.typ = FilterType::Remove;
+                                if id.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.id = Filter::new();
+                                        self.filters.id.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.id.typ = FilterType::Remove;
+                                    }
+                                    self.filters.id.push(achievement.id.clone());
                                 }
-                                self.filters.id.push(achievement.id.clone());
-                            }
-                            if title.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.title = Filter::new();
-                                    self.filters.title.typ = FilterType::Include;
-                                } else {
-                                    self.filters.title.typ = FilterType::Remove;
+                                if title.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.title = Filter::new();
+                                        self.filters.title.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.title.typ = FilterType::Remove;
+                                    }
+                                    self.filters.title.push(achievement.title.clone());
                                 }
-                                self.filters.title.push(achievement.title.clone());
-                            }
-                            if deadline.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.deadline = Filter::new();
-                                    self.filters.deadline.typ = FilterType::Include;
-                                } else {
-                                    self.filters.deadline.typ = FilterType::Remove;
+                                if deadline.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.deadline = Filter::new();
+                                        self.filters.deadline.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.deadline.typ = FilterType::Remove;
+                                    }
+                                    self.filters.deadline.push(achievement.deadline);
                                 }
-                                self.filters.deadline.push(achievement.deadline.clone());
-                            }
-                            if done.inner.clicked_by(egui::PointerButton::Secondary) {
-                                if  ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.done = Filter::new();
-                                    self.filters.done.typ = FilterType::Include;
-                                } else {
-                                    self.filters.done.typ = FilterType::Remove;
+                                if let Some(deadline) = picked_deadline {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.deadline = deadline
+                                    });
                                 }
-                                self.filters.done.push(achievement.done);
-                            }
-                            if present_soon.inner.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.present_soon = Filter::new();
-                                    self.filters.present_soon.typ = FilterType::Include;
-                                } else {
-                                    self.filters.present_soon.typ = FilterType::Remove;
+                                if let Some(grade) = picked_grade {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.grade = grade
+                                    });
                                 }
-                                self.filters.present_soon.push(achievement.present_soon);
-                            }
-                            if grade.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.grade = Filter::new();
-                                    self.filters.grade.typ = FilterType::Include;
-                                } else {
-                                    self.filters.grade.typ = FilterType::Remove;
+                                if let Some(presenting_type) = picked_presenting_type {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.presenting_type = presenting_type
+                                    });
                                 }
-                                self.filters.grade.push(achievement.grade);
-                            }
-                            if presenting_type.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.presenting_type = Filter::new();
-                                    self.filters.presenting_type.typ = FilterType::Include;
-                                } else {
-                                    self.filters.presenting_type.typ = FilterType::Remove;
+                                if let Some(programming_language) = picked_programming_language {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.programming_language = programming_language
+                                    });
                                 }
-                                self.filters.presenting_type.push(achievement.presenting_type.clone());
-                            }
-                            if programming_language.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.programming_language = Filter::new();
-                                    self.filters.programming_language.typ = FilterType::Include;
-                                } else {
-                                    self.filters.programming_language.typ = FilterType::Remove;
+                                if let Some(sprint) = picked_sprint {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.sprint = sprint
+                                    });
                                 }
-                                self.filters.programming_language.push(achievement.programming_language.clone());
-                            }
-                            if sprint.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.sprint = Filter::new();
-                                    self.filters.sprint.typ = FilterType::Include;
-                                } else {
-                                    self.filters.sprint.typ = FilterType::Remove;
+                                if let Some(comment) = picked_comment {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.comment = comment
+                                    });
                                 }
-                                self.filters.sprint.push(achievement.sprint.clone());
-                            }
-                            if comment.clicked_by(egui::PointerButton::Secondary) {
-                                if ctx.input(|i| i.modifiers.shift) {
-                                    self.filters.comment = Filter::new();
-                                    self.filters.comment.typ = FilterType::Include;
-                                } else {
-                                    self.filters.comment.typ = FilterType::Remove;
+                                if done_checkbox.inner.changed() {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.done = done
+                                    });
+                                }
+                                if present_soon_checkbox.inner.changed() {
+                                    self.edit_achievement(&achievement.id, |achievement| {
+                                        achievement.present_soon = present_soon
+                                    });
+                                }
+                                if done_checkbox
+                                    .inner
+                                    .clicked_by(egui::PointerButton::Secondary)
+                                {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.done = Filter::new();
+                                        self.filters.done.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.done.typ = FilterType::Remove;
+                                    }
+                                    self.filters.done.push(achievement.done);
                                 }
-                                self.filters.comment.push(achievement.comment.clone());
+                                if present_soon_checkbox
+                                    .inner
+                                    .clicked_by(egui::PointerButton::Secondary)
+                                {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.present_soon = Filter::new();
+                                        self.filters.present_soon.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.present_soon.typ = FilterType::Remove;
+                                    }
+                                    self.filters.present_soon.push(achievement.present_soon);
+                                }
+                                if grade.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.grade = Filter::new();
+                                        self.filters.grade.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.grade.typ = FilterType::Remove;
+                                    }
+                                    self.filters.grade.push(achievement.grade);
+                                }
+                                if presenting_type.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.presenting_type = Filter::new();
+                                        self.filters.presenting_type.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.presenting_type.typ = FilterType::Remove;
+                                    }
+                                    self.filters
+                                        .presenting_type
+                                        .push(achievement.presenting_type.clone());
+                                }
+                                if programming_language.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.programming_language = Filter::new();
+                                        self.filters.programming_language.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.programming_language.typ = FilterType::Remove;
+                                    }
+                                    self.filters
+                                        .programming_language
+                                        .push(achievement.programming_language.clone());
+                                }
+                                if sprint.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.sprint = Filter::new();
+                                        self.filters.sprint.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.sprint.typ = FilterType::Remove;
+                                    }
+                                    self.filters.sprint.push(achievement.sprint.clone());
+                                }
+                                if comment.clicked_by(egui::PointerButton::Secondary) {
+                                    if ctx.input(|i| i.modifiers.shift) {
+                                        self.filters.comment = Filter::new();
+                                        self.filters.comment.typ = FilterType::Include;
+                                    } else {
+                                        self.filters.comment.typ = FilterType::Remove;
+                                    }
+                                    self.filters.comment.push(achievement.comment.clone());
+                                }
+                                ui.end_row();
                             }
-                            ui.end_row();
-                        }
-                    });
+                        });
                 });
         });
     }
@@ -1,6 +1,6 @@
-use chrono::{DateTime, Local};
+use crate::course::Course;
 
-use serde;
+use chrono::{DateTime, Local};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize)]
 pub enum ProgrammingLanguage {
@@ -47,24 +47,42 @@ impl std::fmt::Display for AchievementLanguage {
 }
 
 impl AchievementLanguage {
-    pub fn from_string(string: String) -> AchievementLanguage {
-        match string.as_str() {
-            "C" => AchievementLanguage::Single(ProgrammingLanguage::C),
-            "Java" => AchievementLanguage::Single(ProgrammingLanguage::Java),
-            "Terminal" => AchievementLanguage::Single(ProgrammingLanguage::Bash),
-            "Git" => AchievementLanguage::Single(ProgrammingLanguage::Git),
-            "Essä" => AchievementLanguage::Single(ProgrammingLanguage::NoLanguage),
-            "Möte" => AchievementLanguage::Single(ProgrammingLanguage::NoLanguage),
-            "C & Java" => AchievementLanguage::Both {
+    /// Every combination that can be written to and read back from an achievements file.
+    pub const ALL: [AchievementLanguage; 7] = [
+        AchievementLanguage::Single(ProgrammingLanguage::C),
+        AchievementLanguage::Single(ProgrammingLanguage::Java),
+        AchievementLanguage::Single(ProgrammingLanguage::Git),
+        AchievementLanguage::Single(ProgrammingLanguage::Bash),
+        AchievementLanguage::Single(ProgrammingLanguage::NoLanguage),
+        AchievementLanguage::Both {
+            first: ProgrammingLanguage::C,
+            second: ProgrammingLanguage::Java,
+        },
+        AchievementLanguage::Either {
+            first: ProgrammingLanguage::C,
+            second: ProgrammingLanguage::Java,
+        },
+    ];
+
+    /// Accepts both the spelling written by this app and the one used in the Google Sheets export.
+    pub fn from_string(string: &str) -> Option<AchievementLanguage> {
+        match string {
+            "C" => Some(AchievementLanguage::Single(ProgrammingLanguage::C)),
+            "Java" => Some(AchievementLanguage::Single(ProgrammingLanguage::Java)),
+            "Terminal" => Some(AchievementLanguage::Single(ProgrammingLanguage::Bash)),
+            "Git" => Some(AchievementLanguage::Single(ProgrammingLanguage::Git)),
+            "Essä" => Some(AchievementLanguage::Single(ProgrammingLanguage::NoLanguage)),
+            "Möte" => Some(AchievementLanguage::Single(ProgrammingLanguage::NoLanguage)),
+            "C & Java" | "C&Java" => Some(AchievementLanguage::Both {
                 first: ProgrammingLanguage::C,
                 second: ProgrammingLanguage::Java,
-            },
-            "C / Java" => AchievementLanguage::Either {
+            }),
+            "C / Java" | "C/Java" => Some(AchievementLanguage::Either {
                 first: ProgrammingLanguage::C,
                 second: ProgrammingLanguage::Java,
-            },
-            "NoLanguage" => AchievementLanguage::Single(ProgrammingLanguage::NoLanguage),
-            e => panic!("Unknown programming language {e}"),
+            }),
+            "NoLanguage" => Some(AchievementLanguage::Single(ProgrammingLanguage::NoLanguage)),
+            _ => None,
         }
     }
 }
@@ -81,45 +99,32 @@ pub enum Sprint {
 }
 
 impl Sprint {
-    pub fn to_date(&self) -> DateTime<Local> {
-        match self {
-            Sprint::Lab => {
-                DateTime::parse_from_str("2 Sep 2024 0:0:0 +0000", "%d %b %Y %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local)
-            }
-            Sprint::Sprint1 => {
-                DateTime::parse_from_str("16 Sep 2024 0:0:0 +0000", "%d %b %Y %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local)
-            }
-            Sprint::Sprint2 => {
-                DateTime::parse_from_str("7 Oct 2024 0:0:0 +0000", "%d %b %Y %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local)
-            }
-            Sprint::Sprint3 => {
-                DateTime::parse_from_str("4 Nov 2024 0:0:0 +0000", "%d %b %Y %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local)
-            }
-            Sprint::Sprint4 => {
-                DateTime::parse_from_str("25 Nov 2024 0:0:0 +0000", "%d %b %Y %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local)
-            }
-            Sprint::Project => {
-                DateTime::parse_from_str("2 Dec 2024 0:0:0 +0000", "%d %b %Y %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local)
-            }
-            Sprint::Unclear => {
-                DateTime::parse_from_str("2 Dec 2024 0:0:0 +0000", "%d %b %Y %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local)
-            }
+    pub const ALL: [Sprint; 7] = [
+        Sprint::Lab,
+        Sprint::Sprint1,
+        Sprint::Sprint2,
+        Sprint::Sprint3,
+        Sprint::Sprint4,
+        Sprint::Project,
+        Sprint::Unclear,
+    ];
+
+    pub fn from_string(string: &str) -> Option<Sprint> {
+        match string {
+            "Lab" => Some(Sprint::Lab),
+            "Sprint1" | "Sprint 1" => Some(Sprint::Sprint1),
+            "Sprint2" | "Sprint 2" => Some(Sprint::Sprint2),
+            "Sprint3" | "Sprint 3" => Some(Sprint::Sprint3),
+            "Sprint4" | "Sprint 4" => Some(Sprint::Sprint4),
+            "Project" | "Projekt" => Some(Sprint::Project),
+            "Unclear" | "IDK" => Some(Sprint::Unclear),
+            _ => None,
         }
     }
+
+    pub fn to_date(&self, course: &Course) -> DateTime<Local> {
+        course.sprint_start(self)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize)]
@@ -160,22 +165,53 @@ impl std::fmt::Display for AchievementPresention {
 }
 
 impl AchievementPresention {
-    pub fn from_string(string: String) -> AchievementPresention {
-        match string.as_str() {
-            "Lab" => AchievementPresention::Single(PresentationType::Lab),
-            "Studium" => AchievementPresention::Single(PresentationType::Studium),
-            "Special" => AchievementPresention::Single(PresentationType::Special),
-            "Lab, Studium" => AchievementPresention::Either {
+    /// Every way of presenting that can be written to and read back from an achievements file.
+    pub const ALL: [AchievementPresention; 5] = [
+        AchievementPresention::Single(PresentationType::Lab),
+        AchievementPresention::Single(PresentationType::Studium),
+        AchievementPresention::Single(PresentationType::Special),
+        AchievementPresention::Single(PresentationType::Report),
+        AchievementPresention::Either {
+            first: PresentationType::Lab,
+            second: PresentationType::Studium,
+        },
+    ];
+
+    pub fn from_string(string: &str) -> Option<AchievementPresention> {
+        match string {
+            "Lab" => Some(AchievementPresention::Single(PresentationType::Lab)),
+            "Studium" => Some(AchievementPresention::Single(PresentationType::Studium)),
+            "Special" => Some(AchievementPresention::Single(PresentationType::Special)),
+            "Lab, Studium" => Some(AchievementPresention::Either {
                 first: PresentationType::Lab,
                 second: PresentationType::Studium,
-            },
-            "Report" => AchievementPresention::Single(PresentationType::Report),
-            _ => panic!("Unknown presentation type"),
+            }),
+            "Report" => Some(AchievementPresention::Single(PresentationType::Report)),
+            _ => None,
         }
     }
 }
 
-#[derive(Clone, Debug)]
+/// A value in an achievements or course file that could not be understood.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AchievementError {
+    /// The line in the file, counting the header as line 1.
+    pub row: usize,
+    pub column: &'static str,
+    pub value: String,
+}
+
+impl std::fmt::Display for AchievementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Row {}: invalid {} \"{}\"",
+            self.row, self.column, self.value
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Achievement {
     pub id: String,
     pub title: String,
@@ -1,16 +1,39 @@
 use eframe::CreationContext;
-use serde_json;
 
-use crate::achievement_csv;
-use crate::achievements::*;
-use crate::git;
+use crate::git_worker::{GitJob, GitOutcome, GitWorker, SyncStatus};
 use crate::langs;
-use crate::progress_tracker::ProgressTracker;
-
-use std::fmt::Display;
+use crate::presets::{self, Preset};
+use achievements_enhanced::achievement_csv::ACHIEVEMENTS_FILE;
+use achievements_enhanced::achievements::*;
+use achievements_enhanced::burndown::BurndownPoint;
+use achievements_enhanced::commit_message;
+use achievements_enhanced::git::GitRepository;
+use achievements_enhanced::merge::PendingMerge;
+use achievements_enhanced::progress_tracker::ProgressTrackerMode;
+pub use achievements_enhanced::query::{Filter, FilterType, Filters};
+use achievements_enhanced::simulation::Simulation;
+pub use achievements_enhanced::sort::{Direction, Fieled, Sort};
+use achievements_enhanced::store::AchievementStore;
+
+use std::path::{Path, PathBuf};
 
 use eframe::egui;
 
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// The keys that apply presets together with Ctrl, for the digits 1 to 9.
+pub const DIGITS: [egui::Key; 9] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Settings {
     pub font_size: f32,
@@ -18,246 +41,173 @@ pub struct Settings {
     pub dark_mode: bool,
     pub language: langs::Langs,
     pub git: bool,
+    #[serde(default = "Settings::default_courses")]
+    pub courses: Vec<PathBuf>,
+    #[serde(default)]
+    pub last_course: usize,
+    /// Show the generated commit message for editing before the final sync on exit.
+    #[serde(default)]
+    pub edit_commit_message: bool,
+    /// The highest grade the progress tracker plans for.
+    #[serde(default = "Settings::default_target_grade")]
+    pub target_grade: i8,
+    #[serde(default = "Settings::default_max_per_lab")]
+    pub max_per_lab: u8,
+    #[serde(default)]
+    pub progress_tracker_mode: ProgressTrackerMode,
+    /// Where settings.json lives. Course directories are relative to it.
+    #[serde(skip)]
+    pub data_dir: PathBuf,
 }
 
-impl Settings {
-    pub fn save(&self) {
-        let settings = serde_json::to_string(self).unwrap();
-        let _ = std::fs::write("settings.json", settings);
-    }
-
-    pub fn new() -> Self {
-        let file = std::fs::read("settings.json");
-        match file {
-            Ok(v) => match serde_json::from_slice(&v) {
-                Ok(v) => v,
-                Err(_) => Settings {
-                    font_size: 14.0,
-                    show_passed_labs: false,
-                    dark_mode: true,
-                    language: langs::Langs::English,
-                    git: false,
-                },
-            },
-            Err(_) => Settings {
-                font_size: 14.0,
-                show_passed_labs: false,
-                dark_mode: true,
-                language: langs::Langs::English,
-                git: false,
-            },
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            font_size: 14.0,
+            show_passed_labs: false,
+            dark_mode: true,
+            language: langs::Langs::English,
+            git: false,
+            courses: Settings::default_courses(),
+            last_course: 0,
+            edit_commit_message: false,
+            target_grade: Settings::default_target_grade(),
+            max_per_lab: Settings::default_max_per_lab(),
+            progress_tracker_mode: ProgressTrackerMode::Left,
+            data_dir: PathBuf::from("."),
         }
     }
 }
 
-#[derive(Debug)]
-pub enum Direction {
-    Ascending,
-    Descending,
-    Default,
-}
-
-impl Display for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            Direction::Ascending => write!(f, "ascending"),
-            Direction::Descending => write!(f, "descending"),
-            Direction::Default => write!(f, "default"),
-        }
+impl Settings {
+    pub fn save(&self) {
+        let settings = serde_json::to_string(self).unwrap();
+        let _ = std::fs::write(self.data_dir.join(SETTINGS_FILE), settings);
     }
-}
 
-impl Direction {
-    pub fn reverse(&self) -> Direction {
-        match self {
-            Direction::Ascending => Direction::Descending,
-            Direction::Descending => Direction::Ascending,
-            Direction::Default => Direction::Ascending,
-        }
+    pub fn new(data_dir: &Path) -> Self {
+        let file = std::fs::read(data_dir.join(SETTINGS_FILE));
+        let mut settings: Settings = match file {
+            Ok(v) => serde_json::from_slice(&v).unwrap_or_default(),
+            Err(_) => Settings::default(),
+        };
+        settings.data_dir = data_dir.to_path_buf();
+        settings
     }
-}
 
-#[derive(PartialEq)]
-pub enum Fieled {
-    ID,
-    Title,
-    Deadline,
-    Done,
-    PresentSoon,
-    Grade,
-    PresentingType,
-    ProgrammingLanguage,
-    Sprint,
-    Comment,
-}
-
-pub struct Sort {
-    pub id: Direction,
-    pub title: Direction,
-    pub deadline: Direction,
-    pub done: Direction,
-    pub present_soon: Direction,
-    pub grade: Direction,
-    pub presenting_type: Direction,
-    pub programming_language: Direction,
-    pub sprint: Direction,
-    pub comment: Direction,
-    pub fieled: Fieled,
-}
-
-impl Sort {
-    fn new() -> Self {
-        Self {
-            id: Direction::Default,
-            title: Direction::Default,
-            deadline: Direction::Default,
-            done: Direction::Default,
-            present_soon: Direction::Default,
-            grade: Direction::Default,
-            presenting_type: Direction::Default,
-            programming_language: Direction::Default,
-            sprint: Direction::Default,
-            comment: Direction::Default,
-            fieled: Fieled::ID,
-        }
+    /// The directory of a course, resolving paths relative to the data directory.
+    pub fn course_dir(&self, course: &Path) -> PathBuf {
+        self.data_dir.join(course)
     }
 
-    fn from(fieled: Fieled) -> Self {
-        let mut sort = Self::new();
-        match fieled {
-            Fieled::ID => sort.id = Direction::Ascending,
-            Fieled::Title => sort.title = Direction::Ascending,
-            Fieled::Deadline => sort.deadline = Direction::Ascending,
-            Fieled::Done => sort.done = Direction::Ascending,
-            Fieled::PresentSoon => sort.present_soon = Direction::Ascending,
-            Fieled::Grade => sort.grade = Direction::Ascending,
-            Fieled::PresentingType => sort.presenting_type = Direction::Ascending,
-            Fieled::ProgrammingLanguage => sort.programming_language = Direction::Ascending,
-            Fieled::Sprint => sort.sprint = Direction::Ascending,
-            Fieled::Comment => sort.comment = Direction::Ascending,
-        }
-        sort.fieled = fieled;
-        sort
-    }
-
-    fn reverse(&mut self, fieled: Fieled) {
-        if self.fieled == fieled {
-            match self.fieled {
-                Fieled::ID => self.id = self.id.reverse(),
-                Fieled::Title => self.title = self.title.reverse(),
-                Fieled::Deadline => self.deadline = self.deadline.reverse(),
-                Fieled::Done => self.done = self.done.reverse(),
-                Fieled::PresentSoon => self.present_soon = self.present_soon.reverse(),
-                Fieled::Grade => self.grade = self.grade.reverse(),
-                Fieled::PresentingType => self.presenting_type = self.presenting_type.reverse(),
-                Fieled::ProgrammingLanguage => {
-                    self.programming_language = self.programming_language.reverse()
-                }
-                Fieled::Sprint => self.sprint = self.sprint.reverse(),
-                Fieled::Comment => self.comment = self.comment.reverse(),
-            }
-            return;
-        }
-        *self = Self::from(fieled);
+    /// Opens the course in `course` with the progress tracker set up as configured.
+    pub fn open_course(&self, course: &Path) -> AchievementStore {
+        let mut store = AchievementStore::open(&self.course_dir(course));
+        self.configure(&mut store);
+        store
     }
-}
-
-#[derive(PartialEq)]
-pub enum FilterType {
-    Remove,
-    Include,
-}
 
-pub struct Filter<T> {
-    pub typ: FilterType,
-    pub value: Vec<T>,
-}
-
-impl<T: std::cmp::PartialEq> Filter<T> {
-    pub fn new() -> Self {
-        Self {
-            typ: FilterType::Remove,
-            value: Vec::new(),
-        }
+    pub fn configure(&self, store: &mut AchievementStore) {
+        store.configure_progress(
+            self.max_per_lab,
+            self.target_grade,
+            self.progress_tracker_mode,
+        );
     }
 
-    pub fn push(&mut self, value: T) {
-        self.value.push(value);
+    /// Older settings files predate course workspaces; they tracked the course in the current directory.
+    fn default_courses() -> Vec<PathBuf> {
+        vec![PathBuf::from(".")]
     }
 
-    pub fn contains(&self, value: &T) -> bool {
-        self.value.contains(value)
+    /// Older settings files did not save the progress tracker's parameters; these were its defaults.
+    fn default_target_grade() -> i8 {
+        5
     }
-}
 
-pub struct Filters {
-    pub id: Filter<String>,
-    pub title: Filter<String>,
-    pub deadline: Filter<Option<chrono::DateTime<chrono::Local>>>,
-    pub done: Filter<bool>,
-    pub present_soon: Filter<bool>,
-    pub grade: Filter<i8>,
-    pub presenting_type: Filter<AchievementPresention>,
-    pub programming_language: Filter<AchievementLanguage>,
-    pub sprint: Filter<Sprint>,
-    pub comment: Filter<Option<String>>,
-}
-
-impl Filters {
-    pub fn new() -> Self {
-        Self {
-            id: Filter::new(),
-            title: Filter::new(),
-            deadline: Filter::new(),
-            done: Filter::new(),
-            present_soon: Filter::new(),
-            grade: Filter::new(),
-            presenting_type: Filter::new(),
-            programming_language: Filter::new(),
-            sprint: Filter::new(),
-            comment: Filter::new(),
-        }
+    fn default_max_per_lab() -> u8 {
+        4
     }
 }
 
 pub enum ActiveWindow {
     Achievements,
     ProgressTracker,
+    Burndown,
     Settings,
 }
 
+/// A change to every achievement that waits for the user to confirm it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BulkEdit {
+    ClearDone,
+    ClearPresentSoon,
+}
+
 pub struct Application {
     pub settings: Settings,
-    pub achievements: Vec<Achievement>,
-    pub progress_tracker: ProgressTracker,
+    pub workspaces: Vec<AchievementStore>,
+    pub active_workspace: usize,
     pub sorting: Sort,
     pub filters: Filters,
     pub active_window: ActiveWindow,
     pub language: langs::Language,
+    pub new_course: String,
+    pub git_worker: GitWorker,
+    pub sync_status: SyncStatus,
+    /// The achievements of every workspace as they were saved when the running git job started.
+    pub sync_snapshots: Vec<Vec<Achievement>>,
+    /// The window is waiting for a final sync before it closes.
+    pub closing: bool,
+    pub close_confirmed: bool,
+    /// Conflicted achievements files from the last pull, waiting for the user to pick sides.
+    pub pending_merges: Vec<PendingMerge>,
+    /// Whether the merge should be pushed once it is committed.
+    pub merge_push: bool,
+    /// The message being edited before the final sync on exit.
+    pub commit_message: Option<String>,
+    /// The active course's burndown, read from git history when the burndown view is first shown.
+    pub burndown: Option<Result<Vec<BurndownPoint>, String>>,
+    /// The course whose burndown the git worker is reading.
+    pub loading_burndown: Option<PathBuf>,
+    /// Grades left out of the burndown chart.
+    pub hidden_grades: Vec<i8>,
+    /// A what-if copy of the active course in the progress tracker, if one is open.
+    pub simulation: Option<Simulation>,
+    pub editing_calendar: bool,
+    pub new_lab: String,
+    /// Why the date typed in [`Application::new_lab`] could not be added.
+    pub new_lab_error: Option<String>,
+    pub confirming: Option<BulkEdit>,
+    /// The active course's saved filters and sort orders.
+    pub presets: Vec<Preset>,
+    pub new_preset: String,
+    pub editing_presets: bool,
+    /// The query in the search bar, while it is being edited.
+    pub search: String,
+    pub search_error: Option<String>,
 }
 
 impl Application {
-    pub fn new(_cc: &CreationContext) -> Self {
-        let settings = Settings::new();
-        if settings.git {
-            git::git_pull();
-        }
+    pub fn new(cc: &CreationContext, data_dir: PathBuf) -> Self {
+        let mut settings = Settings::new(&data_dir);
 
-        let achievements = match achievement_csv::read_achievements_from_gui() {
-            Ok(achievements) => achievements,
-            Err(_) => match achievement_csv::read_achievements_from_google_sheets() {
-                Ok(achievements) => achievements,
-                Err(_) => achievement_csv::read_defaults(),
-            },
-        };
+        if settings.courses.is_empty() {
+            settings.courses = Settings::default_courses();
+        }
+        let workspaces = settings
+            .courses
+            .iter()
+            .map(|path| settings.open_course(path))
+            .collect::<Vec<AchievementStore>>();
+        let active_workspace = settings.last_course.min(workspaces.len() - 1);
 
-        let progress_tracker = ProgressTracker::new(4, 5, &achievements);
         let language = settings.language;
 
-        Self {
+        let mut application = Self {
             settings,
-            achievements,
-            progress_tracker,
+            workspaces,
+            active_workspace,
             sorting: Sort::new(),
             filters: Filters::new(),
             active_window: ActiveWindow::Achievements,
@@ -265,184 +215,370 @@ impl Application {
                 langs::Langs::English => langs::get_english(),
                 langs::Langs::Swedish => langs::get_swedish(),
             },
+            new_course: String::new(),
+            git_worker: GitWorker::new(cc.egui_ctx.clone(), data_dir),
+            sync_status: SyncStatus::default(),
+            sync_snapshots: Vec::new(),
+            closing: false,
+            close_confirmed: false,
+            pending_merges: Vec::new(),
+            merge_push: false,
+            commit_message: None,
+            burndown: None,
+            loading_burndown: None,
+            hidden_grades: Vec::new(),
+            simulation: None,
+            editing_calendar: false,
+            new_lab: String::new(),
+            new_lab_error: None,
+            confirming: None,
+            presets: Vec::new(),
+            new_preset: String::new(),
+            editing_presets: false,
+            search: String::new(),
+            search_error: None,
+        };
+
+        application.presets = presets::load(&application.workspace().path);
+
+        if application.settings.git {
+            application.start_git(GitJob::Pull);
+        }
+
+        application
+    }
+
+    pub fn workspace(&self) -> &AchievementStore {
+        &self.workspaces[self.active_workspace]
+    }
+
+    pub fn workspace_mut(&mut self) -> &mut AchievementStore {
+        &mut self.workspaces[self.active_workspace]
+    }
+
+    /// Applies the progress tracker settings to every course and saves them.
+    pub fn configure_progress(&mut self) {
+        for workspace in self.workspaces.iter_mut() {
+            self.settings.configure(workspace);
+        }
+        // The simulation keeps its own target grade and labs, but counts the same way.
+        if let Some(simulation) = self.simulation.as_mut() {
+            simulation.set_mode(self.settings.progress_tracker_mode);
+        }
+        self.settings.save();
+    }
+
+    pub fn switch_workspace(&mut self, index: usize) {
+        if index >= self.workspaces.len() || index == self.active_workspace {
+            return;
+        }
+        self.active_workspace = index;
+        self.burndown = None;
+        self.simulation = None;
+        self.sorting = Sort::new();
+        self.filters = Filters::new();
+        self.presets = presets::load(&self.workspace().path);
+        self.settings.last_course = index;
+        self.settings.save();
+    }
+
+    pub fn add_workspace(&mut self, path: PathBuf) {
+        if self.settings.courses.contains(&path) {
+            return;
         }
+        let dir = self.settings.course_dir(&path);
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            eprintln!("Error creating course directory {}: {}", dir.display(), e);
+            return;
+        }
+        self.workspaces.push(self.settings.open_course(&path));
+        self.settings.courses.push(path);
+        self.switch_workspace(self.workspaces.len() - 1);
+    }
+
+    pub fn remove_workspace(&mut self, index: usize) {
+        if self.workspaces.len() <= 1 || index >= self.workspaces.len() {
+            return;
+        }
+        if let Err(e) = self.workspaces[index].save_achievements() {
+            eprintln!("Error saving achievements: {}", e);
+        }
+        self.workspaces.remove(index);
+        self.settings.courses.remove(index);
+        if self.active_workspace >= index && self.active_workspace > 0 {
+            self.active_workspace -= 1;
+        }
+        self.settings.last_course = self.active_workspace;
+        self.settings.save();
     }
 
     pub fn save_achievements(&self) -> Result<(), csv::Error> {
-        let mut wtr = csv::Writer::from_path("achievements.csv")?;
-        for achievement in self.achievements.clone() {
-            let serializable_achievement = SerializableAchievement {
-                id: achievement.id.clone(),
-                link: format!(
-                    "https://uppsala.instructure.com/courses/97453/pages/achievements#{}",
-                    achievement.id[1..].to_string()
-                ),
-                title: achievement.title.clone(),
-                deadline: achievement
-                    .deadline
-                    .map(|x| x.format("%b %d, %Y").to_string()),
-                done: achievement.done,
-                present_soon: achievement.present_soon,
-                grade: achievement.grade,
-                presenting_type: achievement.presenting_type.to_string(),
-                programming_language: achievement.programming_language.to_string(),
-                sprint: achievement.sprint.clone(),
-                comment: achievement.comment.clone(),
-            };
-            wtr.serialize(serializable_achievement)?;
+        for workspace in self.workspaces.iter() {
+            workspace.save_achievements()?;
         }
         Ok(())
     }
 
-    pub fn clear_done(&mut self) {
-        for achievement in self.achievements.iter_mut() {
-            achievement.done = false;
+    /// What the achievements of every workspace changed since the last commit.
+    pub fn describe_changes(&self) -> String {
+        match GitRepository::open(&self.settings.data_dir) {
+            Ok(repo) => commit_message::commit_message(&repo, &self.workspaces),
+            Err(_) => String::from("Update achievements"),
         }
     }
 
-    pub fn clear_present_soon(&mut self) {
-        for achievement in self.achievements.iter_mut() {
-            achievement.present_soon = false;
+    /// Saves every workspace and hands `job` to the git worker,
+    /// committing local changes with a message describing them.
+    pub fn start_git(&mut self, job: GitJob) {
+        let message = self.describe_changes();
+        self.start_git_with_message(job, message);
+    }
+
+    /// Like [`Application::start_git`] with the commit message given.
+    /// Returns `false` if the job could not start, e.g. because another one is running.
+    pub fn start_git_with_message(&mut self, job: GitJob, message: String) -> bool {
+        if self.sync_status.running.is_some() {
+            return false;
         }
+        // Nothing else can be pulled or committed until the merge is finished or aborted.
+        if !self.pending_merges.is_empty() && matches!(job, GitJob::Pull | GitJob::Sync) {
+            return false;
+        }
+        if let Err(e) = self.save_achievements() {
+            self.sync_status.error = Some(e.to_string());
+            return false;
+        }
+        self.sync_snapshots = self
+            .workspaces
+            .iter()
+            .map(|workspace| workspace.achievements.clone())
+            .collect();
+        self.sync_status.running = Some(job.clone());
+        self.sync_status.error = None;
+        self.git_worker.start(job, message);
+        true
     }
 
-    pub fn sort_achievements(&mut self, fieled: Fieled) {
-        self.sorting.reverse(fieled);
-        match self.sorting.fieled {
-            Fieled::ID => match self.sorting.id {
-                Direction::Ascending => self.achievements.sort_by(|a, b| a.id.cmp(&b.id)),
-                Direction::Descending => self.achievements.sort_by(|a, b| b.id.cmp(&a.id)),
-                Direction::Default => self.achievements.sort_by(|a, b| a.id.cmp(&b.id)),
-            },
-            Fieled::Title => match self.sorting.title {
-                Direction::Ascending => self.achievements.sort_by(|a, b| a.title.cmp(&b.title)),
-                Direction::Descending => self.achievements.sort_by(|a, b| b.title.cmp(&a.title)),
-                Direction::Default => self.achievements.sort_by(|a, b| a.title.cmp(&b.title)),
-            },
-            Fieled::Deadline => match self.sorting.deadline {
-                Direction::Ascending => self
-                    .achievements
-                    .sort_by(|a, b| a.deadline.cmp(&b.deadline)),
-                Direction::Descending => self
-                    .achievements
-                    .sort_by(|a, b| b.deadline.cmp(&a.deadline)),
-                Direction::Default => self
-                    .achievements
-                    .sort_by(|a, b| a.deadline.cmp(&b.deadline)),
-            },
-            Fieled::Done => match self.sorting.done {
-                Direction::Ascending => self.achievements.sort_by(|a, b| a.done.cmp(&b.done)),
-                Direction::Descending => self.achievements.sort_by(|a, b| b.done.cmp(&a.done)),
-                Direction::Default => self.achievements.sort_by(|a, b| a.done.cmp(&b.done)),
-            },
-            Fieled::PresentSoon => match self.sorting.present_soon {
-                Direction::Ascending => self
-                    .achievements
-                    .sort_by(|a, b| a.present_soon.cmp(&b.present_soon)),
-                Direction::Descending => self
-                    .achievements
-                    .sort_by(|a, b| b.present_soon.cmp(&a.present_soon)),
-                Direction::Default => self
-                    .achievements
-                    .sort_by(|a, b| a.present_soon.cmp(&b.present_soon)),
-            },
-            Fieled::Grade => match self.sorting.grade {
-                Direction::Ascending => self.achievements.sort_by(|a, b| a.grade.cmp(&b.grade)),
-                Direction::Descending => self.achievements.sort_by(|a, b| b.grade.cmp(&a.grade)),
-                Direction::Default => self.achievements.sort_by(|a, b| a.grade.cmp(&b.grade)),
-            },
-            Fieled::PresentingType => match self.sorting.presenting_type {
-                Direction::Ascending => self
-                    .achievements
-                    .sort_by(|a, b| a.presenting_type.cmp(&b.presenting_type)),
-                Direction::Descending => self
-                    .achievements
-                    .sort_by(|a, b| b.presenting_type.cmp(&a.presenting_type)),
-                Direction::Default => self
-                    .achievements
-                    .sort_by(|a, b| a.presenting_type.cmp(&b.presenting_type)),
-            },
-            Fieled::ProgrammingLanguage => match self.sorting.programming_language {
-                Direction::Ascending => self
-                    .achievements
-                    .sort_by(|a, b| a.programming_language.cmp(&b.programming_language)),
-                Direction::Descending => self
-                    .achievements
-                    .sort_by(|a, b| b.programming_language.cmp(&a.programming_language)),
-                Direction::Default => self
-                    .achievements
-                    .sort_by(|a, b| a.programming_language.cmp(&b.programming_language)),
-            },
-            Fieled::Sprint => match self.sorting.sprint {
-                Direction::Ascending => self.achievements.sort_by(|a, b| a.sprint.cmp(&b.sprint)),
-                Direction::Descending => self.achievements.sort_by(|a, b| b.sprint.cmp(&a.sprint)),
-                Direction::Default => self.achievements.sort_by(|a, b| a.sprint.cmp(&b.sprint)),
-            },
-            Fieled::Comment => match self.sorting.comment {
-                Direction::Ascending => self.achievements.sort_by(|a, b| a.comment.cmp(&b.comment)),
-                Direction::Descending => {
-                    self.achievements.sort_by(|a, b| b.comment.cmp(&a.comment))
+    fn poll_git(&mut self, ctx: &egui::Context) {
+        while let Some(event) = self.git_worker.poll() {
+            if let GitJob::Burndown { path } = event.job {
+                // A burndown of a course that is no longer shown is thrown away.
+                if self.loading_burndown.as_ref() == Some(&path) {
+                    self.loading_burndown = None;
+                    self.burndown = match event.result {
+                        Ok(GitOutcome::Burndown(points)) => Some(Ok(points)),
+                        Ok(_) => None,
+                        Err(e) => Some(Err(e.to_string())),
+                    };
                 }
-                Direction::Default => self.achievements.sort_by(|a, b| a.comment.cmp(&b.comment)),
-            },
+                continue;
+            }
+            self.sync_status.running = None;
+            match event.result {
+                Ok(GitOutcome::Done) => {
+                    self.sync_status.last_sync = Some(chrono::Local::now());
+                    // New commits may have moved the burndown.
+                    self.burndown = None;
+                    for (workspace, snapshot) in
+                        self.workspaces.iter_mut().zip(self.sync_snapshots.iter())
+                    {
+                        workspace.reload(snapshot);
+                    }
+                    if self.closing {
+                        match event.job {
+                            GitJob::Sync
+                            | GitJob::CompleteMerge { push: true }
+                            | GitJob::AbortMerge => {
+                                self.close_confirmed = true;
+                                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                            }
+                            _ => self.start_git(GitJob::Sync),
+                        }
+                    }
+                }
+                Ok(GitOutcome::Conflicts(merges)) => {
+                    self.merge_push = event.job == GitJob::Sync;
+                    self.pending_merges = merges;
+                    // Everything merged on its own, there is nothing to ask the user.
+                    if self
+                        .pending_merges
+                        .iter()
+                        .all(|merge| merge.conflicts.is_empty())
+                    {
+                        self.apply_merges();
+                    }
+                }
+                Ok(GitOutcome::Burndown(_)) => {}
+                Err(e) => {
+                    eprintln!("Error syncing with git: {}", e);
+                    self.sync_status.error = Some(e.to_string());
+                }
+            }
         }
     }
 
+    /// Puts the resolved achievements into their workspaces and commits the merge.
+    pub fn apply_merges(&mut self) {
+        for merge in std::mem::take(&mut self.pending_merges) {
+            let file = std::fs::canonicalize(&merge.file).ok();
+            let Some(workspace) = self.workspaces.iter_mut().find(|workspace| {
+                std::fs::canonicalize(workspace.path.join(ACHIEVEMENTS_FILE)).ok() == file
+            }) else {
+                eprintln!(
+                    "No course uses {}, keeping our version",
+                    merge.file.display()
+                );
+                // Replace the conflict markers git left in the file, they must not be committed.
+                if let Err(e) = std::fs::write(&merge.file, &merge.ours) {
+                    eprintln!("Error writing {}: {}", merge.file.display(), e);
+                    self.abort_merges();
+                    return;
+                }
+                continue;
+            };
+            workspace.set_achievements(merge.resolve());
+        }
+        self.start_git(GitJob::CompleteMerge {
+            push: self.merge_push,
+        });
+    }
+
+    /// Throws away the pulled changes and goes back to our last commit.
+    pub fn abort_merges(&mut self) {
+        self.pending_merges.clear();
+        self.start_git(GitJob::AbortMerge);
+    }
+
+    /// Makes a change to the active course that goes into its undo history,
+    /// and saves the course right away if `change` reports that anything changed.
+    pub fn change_workspace(&mut self, change: impl FnOnce(&mut AchievementStore) -> bool) {
+        if change(self.workspace_mut()) {
+            if let Err(e) = self.workspace().save_achievements() {
+                eprintln!("Error saving achievements: {}", e);
+            }
+        }
+    }
+
+    /// Applies an edit made in the achievements grid and saves it right away.
+    pub fn edit_achievement(&mut self, id: &str, change: impl FnOnce(&mut Achievement)) {
+        self.change_workspace(|workspace| workspace.edit(id, change));
+    }
+
+    /// Takes back the last change to the active course's achievements and saves the result.
+    pub fn undo(&mut self) {
+        self.change_workspace(AchievementStore::undo);
+    }
+
+    pub fn redo(&mut self) {
+        self.change_workspace(AchievementStore::redo);
+    }
+
+    pub fn clear_done(&mut self) {
+        self.change_workspace(|workspace| {
+            workspace.edit_all(|achievement| achievement.done = false)
+        });
+    }
+
+    pub fn clear_present_soon(&mut self) {
+        self.change_workspace(|workspace| {
+            workspace.edit_all(|achievement| achievement.present_soon = false)
+        });
+    }
+
+    /// Saves the filters and sort order in use as a preset called `name`,
+    /// replacing the preset of that name if there is one.
+    pub fn save_preset(&mut self, name: &str) {
+        let preset = Preset {
+            name: name.to_string(),
+            query: self.filters.to_string(),
+            sort: self.sorting.keys.clone(),
+            key: None,
+        };
+        match self.presets.iter_mut().find(|preset| preset.name == name) {
+            Some(existing) => {
+                *existing = Preset {
+                    key: existing.key,
+                    ..preset
+                }
+            }
+            None => self.presets.push(preset),
+        }
+        presets::save(&self.workspace().path, &self.presets);
+    }
+
+    pub fn apply_preset(&mut self, index: usize) {
+        let Some(preset) = self.presets.get(index).cloned() else {
+            return;
+        };
+        match Filters::parse(&preset.query) {
+            Ok(filters) => self.filters = filters,
+            Err(e) => {
+                eprintln!("Error in preset {}: {}", preset.name, e);
+                return;
+            }
+        }
+        self.sorting = Sort { keys: preset.sort };
+        self.apply_sorting();
+    }
+
+    pub fn remove_preset(&mut self, index: usize) {
+        if index < self.presets.len() {
+            self.presets.remove(index);
+            presets::save(&self.workspace().path, &self.presets);
+        }
+    }
+
+    /// Binds Ctrl and the digit `key` to the preset at `index`, taking it from any other preset.
+    pub fn set_preset_key(&mut self, index: usize, key: Option<u8>) {
+        presets::set_key(&mut self.presets, index, key);
+        presets::save(&self.workspace().path, &self.presets);
+    }
+
+    /// Sorts by `fieled`, or with `add` by `fieled` after the fields sorted by already.
+    pub fn sort_achievements(&mut self, fieled: Fieled, add: bool) {
+        self.sorting.click(fieled, add);
+        self.apply_sorting();
+    }
+
+    /// Puts the active course's achievements in the order of [`Application::sorting`].
+    pub fn apply_sorting(&mut self) {
+        let sorting = self.sorting.clone();
+        sorting.sort(&mut self.workspace_mut().achievements);
+        // The plan keeps the order of the achievements between equals.
+        self.workspace_mut().update_progress();
+    }
+
     pub fn filtered_achievements(&mut self) -> Vec<(usize, Achievement)> {
-        let filtered_achievements = self
+        self.workspace()
             .achievements
             .clone()
             .into_iter()
             .enumerate()
-            .filter(|(_, x)| {
-                (self.filters.id.typ == FilterType::Include) == self.filters.id.contains(&x.id)
-            })
-            .filter(|(_, x)| {
-                (self.filters.title.typ == FilterType::Include)
-                    == self.filters.title.contains(&x.title)
-            })
-            .filter(|(_, x)| {
-                (self.filters.deadline.typ == FilterType::Include)
-                    == self.filters.deadline.contains(&x.deadline)
-            })
-            .filter(|(_, x)| {
-                (self.filters.done.typ == FilterType::Include)
-                    == self.filters.done.contains(&x.done)
-            })
-            .filter(|(_, x)| {
-                (self.filters.present_soon.typ == FilterType::Include)
-                    == self.filters.present_soon.contains(&x.present_soon)
-            })
-            .filter(|(_, x)| {
-                (self.filters.grade.typ == FilterType::Include)
-                    == self.filters.grade.contains(&x.grade)
-            })
-            .filter(|(_, x)| {
-                (self.filters.presenting_type.typ == FilterType::Include)
-                    == self.filters.presenting_type.contains(&x.presenting_type)
-            })
-            .filter(|(_, x)| {
-                (self.filters.programming_language.typ == FilterType::Include)
-                    == self
-                        .filters
-                        .programming_language
-                        .contains(&x.programming_language)
-            })
-            .filter(|(_, x)| {
-                (self.filters.sprint.typ == FilterType::Include)
-                    == self.filters.sprint.contains(&x.sprint)
-            })
-            .filter(|(_, x)| {
-                (self.filters.comment.typ == FilterType::Include)
-                    == self.filters.comment.contains(&x.comment)
-            })
-            .collect::<Vec<(usize, Achievement)>>();
-
-        filtered_achievements
+            .filter(|(_, x)| self.filters.matches(x))
+            .collect::<Vec<(usize, Achievement)>>()
     }
 }
 
 impl Application {
+    /// The heading of the column of `fieled`, with an arrow if it is sorted by
+    /// and its place among the fields sorted by if there are several.
+    pub fn sort_heading(&self, ui: &mut egui::Ui, fieled: Fieled, text: &str) -> egui::Response {
+        let heading = match self.sorting.position(fieled) {
+            Some((i, direction)) => {
+                let arrow = match direction {
+                    Direction::Ascending => "⏶",
+                    Direction::Descending => "⏷",
+                };
+                match self.sorting.keys.len() {
+                    1 => format!("{} {}", text, arrow),
+                    _ => format!("{} {}{}", text, arrow, i + 1),
+                }
+            }
+            None => text.to_string(),
+        };
+        self.heading(ui, heading)
+    }
+
     pub fn heading(&self, ui: &mut egui::Ui, text: impl Into<String>) -> egui::Response {
         ui.label(egui::RichText::new(text).font(egui::FontId::new(
             self.settings.font_size * 1.5,
@@ -456,15 +592,12 @@ impl eframe::App for Application {
         if let Err(e) = self.save_achievements() {
             eprintln!("Error saving achievements: {}", e);
         };
-        if self.settings.git {
-            git::git_add();
-            git::git_commit();
-            git::git_push();
-        }
     }
 
     fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
         let mut close = false;
+        // A focused text field keeps Ctrl+Z to itself.
+        let typing = ctx.wants_keyboard_input();
         ctx.input(|i| {
             if i.modifiers.ctrl {
                 if i.key_pressed(egui::Key::Q) {
@@ -477,14 +610,27 @@ impl eframe::App for Application {
                     self.active_window = ActiveWindow::Achievements;
                 } else if i.key_pressed(egui::Key::P) {
                     self.active_window = ActiveWindow::ProgressTracker;
+                } else if i.key_pressed(egui::Key::B) {
+                    self.active_window = ActiveWindow::Burndown;
                 } else if i.key_pressed(egui::Key::F)
                     || i.pointer.button_clicked(egui::PointerButton::Middle)
                 {
                     self.filters = Filters::new();
                 } else if i.key_pressed(egui::Key::D) {
-                    self.clear_done();
+                    self.confirming = Some(BulkEdit::ClearDone);
                 } else if i.key_pressed(egui::Key::L) {
-                    self.clear_present_soon();
+                    self.confirming = Some(BulkEdit::ClearPresentSoon);
+                } else if let Some(digit) = DIGITS.iter().position(|key| i.key_pressed(*key)) {
+                    let key = Some(digit as u8 + 1);
+                    if let Some(index) = self.presets.iter().position(|preset| preset.key == key) {
+                        self.apply_preset(index);
+                    }
+                } else if i.key_pressed(egui::Key::Z) && !typing {
+                    if i.modifiers.shift {
+                        self.redo();
+                    } else {
+                        self.undo();
+                    }
                 } else if i.key_pressed(egui::Key::Comma) {
                     self.active_window = ActiveWindow::Settings;
                 }
@@ -495,6 +641,26 @@ impl eframe::App for Application {
             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
         }
 
+        self.poll_git(ctx);
+
+        if ctx.input(|i| i.viewport().close_requested())
+            && self.settings.git
+            && !self.close_confirmed
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.closing = true;
+            if !self.pending_merges.is_empty() {
+                // Finish the merge the user is looking at first, pushing it before closing.
+                self.merge_push = true;
+            } else if self.settings.edit_commit_message {
+                if self.commit_message.is_none() {
+                    self.commit_message = Some(self.describe_changes());
+                }
+            } else {
+                self.start_git(GitJob::Sync);
+            }
+        }
+
         ctx.style_mut(|ctx| {
             ctx.override_font_id = Some(egui::FontId::new(
                 self.settings.font_size,
@@ -508,9 +674,16 @@ impl eframe::App for Application {
             false => ctx.set_visuals(egui::Visuals::light()),
         }
 
+        self.recovery_ui(ctx);
+        self.merge_ui(ctx);
+        self.closing_ui(ctx);
+        self.confirm_ui(ctx);
+        self.presets_ui(ctx);
+
         match self.active_window {
             ActiveWindow::Achievements => self.achievements_ui(ctx),
             ActiveWindow::ProgressTracker => self.progress_tracker_ui(ctx),
+            ActiveWindow::Burndown => self.burndown_ui(ctx),
             ActiveWindow::Settings => self.settings_ui(ctx),
         }
     }
@@ -1,71 +1,238 @@
-use crate::git;
+use crate::achievement_csv::{self, ACHIEVEMENTS_FILE};
+use crate::achievements::Achievement;
+use crate::course::Course;
+use crate::git::{GitError, GitRepository, Repository};
 
-use chrono::{DateTime, Local};
-use csv;
-use std::collections::HashMap;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::path::Path;
 
-struct Commit {
-    date: DateTime<Local>,
-    dones: u8,
-    planned: u8,
+pub const BURNDOWN_FILE: &str = "burndown.csv";
+
+#[derive(Debug)]
+pub enum BurndownError {
+    Git(GitError),
+    Csv(csv::Error),
+}
+
+impl std::fmt::Display for BurndownError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BurndownError::Git(e) => write!(f, "{}", e),
+            BurndownError::Csv(e) => write!(f, "{}", e),
+        }
+    }
 }
 
-pub fn generate() {
-    let commits = git::git_get_commits();
+/// How many achievements there were and how far they had come.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counts {
+    pub total: u32,
+    pub done: u32,
+    /// Achievements marked to present soon that are not done yet.
+    pub planned: u32,
+}
 
-    let mut dones: HashMap<DateTime<Local>, u8> = HashMap::new();
-    let mut planned: HashMap<DateTime<Local>, u8> = HashMap::new();
+impl Counts {
+    pub fn remaining(&self) -> u32 {
+        self.total.saturating_sub(self.done)
+    }
 
-    for (commit, date) in commits {
-        let achievements = git::git_get_achievements_from_commit(commit.clone());
+    /// What is left once everything planned has been presented.
+    pub fn remaining_after_planned(&self) -> u32 {
+        self.remaining().saturating_sub(self.planned)
+    }
+
+    fn add(&mut self, other: &Counts) {
+        self.total += other.total;
+        self.done += other.done;
+        self.planned += other.planned;
+    }
+}
 
-        let mut reader = csv::ReaderBuilder::new().from_reader(achievements.as_bytes());
+/// The state of the achievements file at the end of one day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    /// Counts per grade. Rows that could not be parsed and have no readable grade are under `None`.
+    pub grades: BTreeMap<Option<i8>, Counts>,
+}
 
-        for result in reader.records() {
-            let record = result.unwrap();
-            dones.insert(
-                date,
-                dones.get(&date).unwrap_or(&0) + (record[4] == *"true") as u8,
-            );
-            planned.insert(
-                date,
-                planned.get(&date).unwrap_or(&0) + (record[5] == *"true") as u8,
-            );
+impl BurndownPoint {
+    /// The counts for the given grades, or for every row in the file if `grades` is `None`.
+    pub fn counts(&self, grades: Option<&[i8]>) -> Counts {
+        let mut counts = Counts::default();
+        for (grade, grade_counts) in self.grades.iter() {
+            let included = match (grades, grade) {
+                (None, _) => true,
+                (Some(grades), Some(grade)) => grades.contains(grade),
+                (Some(_), None) => false,
+            };
+            if included {
+                counts.add(grade_counts);
+            }
         }
+        counts
     }
+}
+
+/// One point per day that the achievements file in `path` was committed,
+/// taken from the last commit of that day, oldest first.
+pub fn history(repo: &impl Repository, path: &Path) -> Result<Vec<BurndownPoint>, GitError> {
+    let file = path.join(ACHIEVEMENTS_FILE);
+    let mut days: BTreeMap<NaiveDate, BurndownPoint> = BTreeMap::new();
+
+    // Commits come newest first, so the first one read for each day is its last.
+    for (commit, date) in repo.commits()? {
+        let date = date.date_naive();
+        if days.contains_key(&date) {
+            continue;
+        }
+        let Ok(contents) = repo.file_at(&commit, &file) else {
+            continue;
+        };
+        let Ok(file) = achievement_csv::read_achievements_from_str(&contents) else {
+            continue;
+        };
 
-    let mut dones = dones
-        .into_iter()
-        .map(|(date, dones)| (date, 65 - dones))
-        .collect::<Vec<(DateTime<Local>, u8)>>();
-    dones.sort_by(|a, b| a.0.cmp(&b.0));
-
-    let mut planned = planned.into_iter().collect::<Vec<(DateTime<Local>, u8)>>();
-    planned.sort_by(|a, b| a.0.cmp(&b.0));
-
-    planned = planned
-        .into_iter()
-        .enumerate()
-        .map(|(i, (date, doing_soon))| (date, dones[i].1 - doing_soon))
-        .collect::<Vec<(DateTime<Local>, u8)>>();
-
-    let mut commits = Vec::new();
-    for (i, (date, done)) in dones.iter().enumerate() {
-        commits.push(Commit {
-            date: *date,
-            dones: *done,
-            planned: planned[i].1,
-        });
+        let mut grades: BTreeMap<Option<i8>, Counts> = BTreeMap::new();
+        for achievement in file.achievements.iter() {
+            let counts = grades.entry(Some(achievement.grade)).or_default();
+            counts.total += 1;
+            counts.done += achievement.done as u32;
+            counts.planned += (achievement.present_soon && !achievement.done) as u32;
+        }
+        for broken_row in file.broken_rows.iter() {
+            let grade = achievement_csv::COLUMNS
+                .iter()
+                .position(|column| *column == "grade")
+                .and_then(|column| broken_row.record.get(column))
+                .and_then(|grade| grade.trim().parse::<i8>().ok());
+            grades.entry(grade).or_default().total += 1;
+        }
+
+        days.insert(date, BurndownPoint { date, grades });
     }
 
-    let mut csv = csv::Writer::from_path("burndown.csv").unwrap();
-    csv.write_record(&["date", "done", "planned"]).unwrap();
-    for commit in commits {
+    Ok(days.into_values().collect())
+}
+
+/// Where the remaining achievements of the given grades should be if every sprint's
+/// achievements are done by the time the next sprint starts, one point per sprint boundary.
+pub fn ideal(
+    course: &Course,
+    achievements: &[Achievement],
+    grades: &[i8],
+) -> Vec<(NaiveDate, u32)> {
+    let mut boundaries = course
+        .sprints
+        .iter()
+        .map(|(_, start)| start.date_naive())
+        .chain(course.lab_dates().map(|lab| lab.date_naive()).max())
+        .collect::<Vec<NaiveDate>>();
+    boundaries.sort();
+    boundaries.dedup();
+
+    // A sprint ends where the next one starts, the last one when the labs end.
+    let end = |achievement: &Achievement| {
+        let start = achievement.sprint.to_date(course).date_naive();
+        boundaries
+            .iter()
+            .find(|boundary| **boundary > start)
+            .copied()
+            .unwrap_or(start)
+    };
+
+    boundaries
+        .iter()
+        .map(|boundary| {
+            let remaining = achievements
+                .iter()
+                .filter(|achievement| grades.contains(&achievement.grade))
+                .filter(|achievement| end(achievement) > *boundary)
+                .count() as u32;
+            (*boundary, remaining)
+        })
+        .collect()
+}
+
+pub fn write_csv(path: &Path, points: &[BurndownPoint]) -> Result<(), csv::Error> {
+    let mut csv = csv::Writer::from_path(path.join(BURNDOWN_FILE))?;
+    csv.write_record(["date", "done", "planned"])?;
+    for point in points.iter() {
         csv.write_record(&[
-            commit.date.format("%Y-%m-%d").to_string(),
-            commit.dones.to_string(),
-            commit.planned.to_string(),
-        ])
-        .unwrap();
+            point.date.format("%Y-%m-%d").to_string(),
+            point.counts(None).remaining().to_string(),
+            point.counts(None).remaining_after_planned().to_string(),
+        ])?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Reads the burndown of the course in `path` from git history and writes it to burndown.csv.
+pub fn generate(path: &Path) -> Result<Vec<BurndownPoint>, BurndownError> {
+    let repo = GitRepository::open(path).map_err(BurndownError::Git)?;
+    let points = history(&repo, path).map_err(BurndownError::Git)?;
+    write_csv(path, &points).map_err(BurndownError::Csv)?;
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const HEADER: &str =
+        "id,link,title,deadline,done,present_soon,grade,presenting_type,programming_language,sprint,comment\n";
+
+    fn row(id: &str, done: &str, present_soon: &str) -> String {
+        format!(
+            "{},,Title,,{},{},3,Lab,C,Sprint1,\n",
+            id, done, present_soon
+        )
+    }
+
+    fn commit(repo: &GitRepository, dir: &TempDir, contents: &str) {
+        std::fs::write(dir.path().join(ACHIEVEMENTS_FILE), contents).unwrap();
+        repo.add_all().unwrap();
+        repo.commit("update").unwrap();
+    }
+
+    #[test]
+    fn last_commit_of_the_day_wins() {
+        let dir = TempDir::new().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        let repo = GitRepository::open(dir.path()).unwrap();
+
+        commit(
+            &repo,
+            &dir,
+            &(HEADER.to_string() + &row("A1", "false", "false")),
+        );
+        commit(
+            &repo,
+            &dir,
+            &(HEADER.to_string()
+                + &row("A1", "TRUE", "FALSE")
+                + &row("A2", "FALSE", "TRUE")
+                + &row("A3", "true", "true")
+                + "A4,,Title,,maybe,false,4,Lab,C,Sprint1,\n"
+                + "A5,,broken\n"),
+        );
+
+        let points = history(&repo, dir.path()).unwrap();
+        assert_eq!(points.len(), 1);
+        let all = points[0].counts(None);
+        assert_eq!(all.total, 5);
+        assert_eq!(all.done, 2);
+        assert_eq!(all.planned, 1);
+        assert_eq!(all.remaining(), 3);
+        assert_eq!(all.remaining_after_planned(), 2);
+
+        let grade_three = points[0].counts(Some(&[3]));
+        assert_eq!(grade_three.total, 3);
+        assert_eq!(grade_three.remaining(), 1);
+        assert_eq!(points[0].counts(Some(&[4])).total, 1);
     }
 }
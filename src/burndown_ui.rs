@@ -0,0 +1,138 @@
+use crate::application::Application;
+use crate::git_worker::GitJob;
+use achievements_enhanced::burndown;
+
+use chrono::{Datelike, NaiveDate};
+use eframe::egui;
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+fn to_x(date: NaiveDate) -> f64 {
+    date.num_days_from_ce() as f64
+}
+
+fn from_x(x: f64) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(x.round() as i32)
+}
+
+impl Application {
+    /// Has the git worker read the burndown of the active course from git history.
+    /// The burndown shown so far stays until it is done.
+    pub fn load_burndown(&mut self) {
+        let path = self.workspace().path.clone();
+        self.loading_burndown = Some(path.clone());
+        self.git_worker
+            .start(GitJob::Burndown { path }, String::new());
+    }
+
+    pub fn burndown_ui(&mut self, ctx: &egui::Context) {
+        let loading = self.loading_burndown.as_ref() == Some(&self.workspace().path);
+        if self.burndown.is_none() && !loading {
+            self.load_burndown();
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.menu_bar(ctx, ui);
+
+            let mut grades = self
+                .workspace()
+                .achievements
+                .iter()
+                .map(|achievement| achievement.grade)
+                .collect::<Vec<i8>>();
+            grades.sort();
+            grades.dedup();
+
+            ui.horizontal(|ui| {
+                ui.label(self.language.grade.clone());
+                for grade in grades.iter() {
+                    let mut shown = !self.hidden_grades.contains(grade);
+                    if ui.checkbox(&mut shown, grade.to_string()).changed() {
+                        if shown {
+                            self.hidden_grades.retain(|hidden| hidden != grade);
+                        } else {
+                            self.hidden_grades.push(*grade);
+                        }
+                    }
+                }
+                if ui.button(self.language.refresh.clone()).clicked() {
+                    self.load_burndown();
+                }
+                if self.loading_burndown.is_some() {
+                    ui.spinner();
+                }
+            });
+            ui.allocate_space(egui::vec2(0.0, 10.0));
+
+            let points = match &self.burndown {
+                Some(Ok(points)) => points,
+                Some(Err(e)) => {
+                    ui.label(
+                        egui::RichText::new(format!("{}: {}", self.language.no_history, e))
+                            .color(ui.visuals().error_fg_color),
+                    );
+                    return;
+                }
+                None => return,
+            };
+
+            grades.retain(|grade| !self.hidden_grades.contains(grade));
+
+            let remaining = points
+                .iter()
+                .map(|point| {
+                    [
+                        to_x(point.date),
+                        point.counts(Some(&grades)).remaining() as f64,
+                    ]
+                })
+                .collect::<Vec<[f64; 2]>>();
+            let remaining_after_planned = points
+                .iter()
+                .map(|point| {
+                    [
+                        to_x(point.date),
+                        point.counts(Some(&grades)).remaining_after_planned() as f64,
+                    ]
+                })
+                .collect::<Vec<[f64; 2]>>();
+            let ideal = burndown::ideal(
+                &self.workspace().course,
+                &self.workspace().achievements,
+                &grades,
+            )
+            .into_iter()
+            .map(|(date, remaining)| [to_x(date), remaining as f64])
+            .collect::<Vec<[f64; 2]>>();
+
+            Plot::new("Burndown Plot")
+                .legend(Legend::default())
+                .x_axis_formatter(|mark, _range| {
+                    from_x(mark.value)
+                        .map(|date| date.format("%b %d").to_string())
+                        .unwrap_or_default()
+                })
+                .label_formatter(|name, value| {
+                    let date = from_x(value.x)
+                        .map(|date| date.format("%a %b %d, %Y").to_string())
+                        .unwrap_or_default();
+                    if name.is_empty() {
+                        date
+                    } else {
+                        format!("{}\n{}: {}", date, name, value.y.round())
+                    }
+                })
+                .show(ui, |plot_ui| {
+                    plot_ui
+                        .line(Line::new(PlotPoints::from(ideal)).name(self.language.ideal.clone()));
+                    plot_ui.line(
+                        Line::new(PlotPoints::from(remaining))
+                            .name(self.language.remaining.clone()),
+                    );
+                    plot_ui.line(
+                        Line::new(PlotPoints::from(remaining_after_planned))
+                            .name(self.language.remaining_after_planned.clone()),
+                    );
+                });
+        });
+    }
+}
@@ -0,0 +1,128 @@
+use crate::application::Application;
+use achievements_enhanced::course::{self, format_date, Lab};
+
+use chrono::{DateTime, Local};
+use eframe::egui;
+
+/// One change to the lab calendar, picked in the panel and applied once it is drawn.
+/// Labs are referred to by their index, several of them can share a date.
+enum CalendarEdit {
+    Add(DateTime<Local>),
+    Remove(usize),
+    Move(usize, DateTime<Local>),
+    Label(usize, Option<String>),
+    Cancel(usize, bool),
+}
+
+impl Application {
+    /// Side panel for adding, moving, labelling, cancelling and removing labs.
+    pub fn calendar_ui(&mut self, ctx: &egui::Context) {
+        egui::SidePanel::right("Calendar Panel").show(ctx, |ui| {
+            ui.heading(self.language.edit_labs.clone());
+            ui.label(self.language.lab_date_hint.clone());
+
+            let mut edit = None;
+            let mut text_ids = Vec::new();
+            let path = self.workspace().path.clone();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("Calendar Grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(self.language.date.clone());
+                        ui.strong(self.language.label.clone());
+                        ui.strong(self.language.cancelled.clone());
+                        ui.end_row();
+
+                        for (i, lab) in self.workspace().course.labs.clone().into_iter().enumerate()
+                        {
+                            let date = lab.date;
+
+                            // Text being typed is kept in egui's memory until the field loses focus.
+                            let id = ui.make_persistent_id(("Lab Date", &path, i));
+                            text_ids.push(id);
+                            let mut text = ui.data_mut(|data| {
+                                data.get_temp_mut_or_insert_with(id, || format_date(date))
+                                    .clone()
+                            });
+                            if ui.text_edit_singleline(&mut text).lost_focus() {
+                                match course::parse_date(text.trim()) {
+                                    Ok(moved) if moved != date => {
+                                        edit = Some(CalendarEdit::Move(i, moved))
+                                    }
+                                    _ => text = format_date(date),
+                                }
+                            }
+                            ui.data_mut(|data| data.insert_temp(id, text));
+
+                            let id = ui.make_persistent_id(("Lab Label", &path, i));
+                            text_ids.push(id);
+                            let mut text = ui.data_mut(|data| {
+                                data.get_temp_mut_or_insert_with(id, || {
+                                    lab.label.clone().unwrap_or_default()
+                                })
+                                .clone()
+                            });
+                            if ui.text_edit_singleline(&mut text).lost_focus() {
+                                let label =
+                                    Some(text.trim().to_string()).filter(|label| !label.is_empty());
+                                if label != lab.label {
+                                    edit = Some(CalendarEdit::Label(i, label));
+                                }
+                            }
+                            ui.data_mut(|data| data.insert_temp(id, text));
+
+                            let mut cancelled = lab.cancelled;
+                            if ui.checkbox(&mut cancelled, "").changed() {
+                                edit = Some(CalendarEdit::Cancel(i, cancelled));
+                            }
+
+                            if ui.button(self.language.remove.clone()).clicked() {
+                                edit = Some(CalendarEdit::Remove(i));
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    if ui.text_edit_singleline(&mut self.new_lab).changed() {
+                        self.new_lab_error = None;
+                    }
+                    if ui.button(self.language.add_lab.clone()).clicked() {
+                        match course::parse_date(self.new_lab.trim()) {
+                            Ok(date) => {
+                                edit = Some(CalendarEdit::Add(date));
+                                self.new_lab.clear();
+                                self.new_lab_error = None;
+                            }
+                            Err(e) => self.new_lab_error = Some(e.to_string()),
+                        }
+                    }
+                });
+                if let Some(error) = &self.new_lab_error {
+                    ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            });
+
+            if let Some(edit) = edit {
+                let result = self.workspace_mut().edit_calendar(|labs| match edit {
+                    CalendarEdit::Add(date) => labs.push(Lab::new(date)),
+                    CalendarEdit::Remove(i) => {
+                        labs.remove(i);
+                    }
+                    CalendarEdit::Move(i, moved) => labs[i].date = moved,
+                    CalendarEdit::Label(i, label) => labs[i].label = label,
+                    CalendarEdit::Cancel(i, cancelled) => labs[i].cancelled = cancelled,
+                });
+                if let Err(e) = result {
+                    eprintln!("Error saving calendar: {}", e);
+                }
+                // The labs may have been reordered, so the text kept for each index is stale.
+                ui.data_mut(|data| {
+                    for id in text_ids {
+                        data.remove::<String>(id);
+                    }
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,309 @@
+use crate::application::Settings;
+use crate::git_worker::{self, GitJob, GitOutcome};
+use achievements_enhanced::achievement_csv::ACHIEVEMENTS_FILE;
+use achievements_enhanced::achievements::Achievement;
+use achievements_enhanced::burndown;
+use achievements_enhanced::commit_message;
+use achievements_enhanced::git::{GitRepository, Repository};
+use achievements_enhanced::query::Filters;
+use achievements_enhanced::store::AchievementStore;
+
+use std::path::PathBuf;
+
+use chrono::Local;
+use clap::{Parser, Subcommand};
+
+/// Track course achievements. Starts the window unless a command is given.
+#[derive(Parser)]
+#[command(version)]
+pub struct Cli {
+    /// Directory holding settings.json and the courses
+    #[arg(long, global = true)]
+    pub data_dir: Option<PathBuf>,
+    /// Course to work on, by name or directory. Defaults to the last course used
+    #[arg(long, global = true)]
+    pub course: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// List achievements
+    List {
+        /// Only achievements matching this search, e.g. 'grade>=4 lang:C !done "pekare"'.
+        /// Plain words match the id, title or comment
+        #[arg(long)]
+        filter: Option<String>,
+        /// Only achievements of this grade
+        #[arg(long)]
+        grade: Option<i8>,
+        /// Only achievements that are not done
+        #[arg(long)]
+        todo: bool,
+    },
+    /// Mark achievements as done
+    Done {
+        #[arg(required = true)]
+        ids: Vec<String>,
+        /// Mark them as not done instead
+        #[arg(long)]
+        undo: bool,
+    },
+    /// Plan to present achievements soon
+    Plan {
+        #[arg(required = true)]
+        ids: Vec<String>,
+        /// Unplan them instead
+        #[arg(long)]
+        undo: bool,
+    },
+    /// Summarize progress towards the target grade
+    Status,
+    /// Print the burndown and write burndown.csv
+    Burndown,
+    /// Commit local changes, pull and push
+    Sync,
+}
+
+/// Runs `command` without opening a window. Returns the process exit code.
+pub fn run(data_dir: PathBuf, course: Option<String>, command: Command) -> i32 {
+    let settings = Settings::new(&data_dir);
+    let mut stores = settings
+        .courses
+        .iter()
+        .map(|path| settings.open_course(path))
+        .collect::<Vec<AchievementStore>>();
+    if stores.is_empty() {
+        stores.push(settings.open_course(&data_dir));
+    }
+
+    let index = match &course {
+        Some(course) => {
+            let Some(index) = stores.iter().position(|store| {
+                store.course.name == *course
+                    || store.path.ends_with(course)
+                    || store.path == settings.course_dir(course.as_ref())
+            }) else {
+                eprintln!("No course named {}", course);
+                return 1;
+            };
+            index
+        }
+        None => settings.last_course.min(stores.len() - 1),
+    };
+
+    match command {
+        Command::List {
+            filter,
+            grade,
+            todo,
+        } => {
+            let filters = match Filters::parse(filter.as_deref().unwrap_or_default()) {
+                Ok(filters) => filters,
+                Err(e) => {
+                    eprintln!("Invalid filter {}", e);
+                    return 1;
+                }
+            };
+            for achievement in stores[index].query(|achievement| filters.matches(achievement)) {
+                if grade.is_none_or(|grade| achievement.grade == grade)
+                    && !(todo && achievement.done)
+                {
+                    println!("{}", row(achievement));
+                }
+            }
+            0
+        }
+        Command::Done { ids, undo } => mark(&mut stores[index], &ids, |achievement| {
+            achievement.done = !undo
+        }),
+        Command::Plan { ids, undo } => mark(&mut stores[index], &ids, |achievement| {
+            achievement.present_soon = !undo
+        }),
+        Command::Status => {
+            status(&stores[index]);
+            0
+        }
+        Command::Burndown => match burndown::generate(&stores[index].path) {
+            Ok(points) => {
+                println!("date        remaining  after planned");
+                for point in points {
+                    let counts = point.counts(None);
+                    println!(
+                        "{}  {:>9}  {:>13}",
+                        point.date.format("%Y-%m-%d"),
+                        counts.remaining(),
+                        counts.remaining_after_planned()
+                    );
+                }
+                0
+            }
+            Err(e) => {
+                eprintln!("Error generating burndown: {}", e);
+                1
+            }
+        },
+        Command::Sync => sync(&data_dir, &mut stores),
+    }
+}
+
+fn row(achievement: &Achievement) -> String {
+    format!(
+        "{:<4} [{}{}] {} {:<6} {:<10} {:?}  {}{}",
+        achievement.id,
+        if achievement.done { "x" } else { " " },
+        if achievement.present_soon { "p" } else { " " },
+        achievement.grade,
+        achievement.programming_language.to_string(),
+        achievement.presenting_type.to_string(),
+        achievement.sprint,
+        achievement.title,
+        achievement
+            .comment
+            .as_ref()
+            .map(|comment| format!(" ({})", comment))
+            .unwrap_or_default()
+    )
+}
+
+fn mark(store: &mut AchievementStore, ids: &[String], change: impl Fn(&mut Achievement)) -> i32 {
+    let mut code = 0;
+    for id in ids.iter() {
+        if !store.edit(id, &change) {
+            eprintln!("No achievement {}", id);
+            code = 1;
+        }
+    }
+    if let Err(e) = store.save_achievements() {
+        eprintln!("Error saving achievements: {}", e);
+        return 1;
+    }
+    code
+}
+
+fn status(store: &AchievementStore) {
+    println!("{}", store.course.name);
+
+    let mut grades = store
+        .achievements
+        .iter()
+        .map(|achievement| achievement.grade)
+        .collect::<Vec<i8>>();
+    grades.sort();
+    grades.dedup();
+    for grade in grades {
+        let achievements = store
+            .query(|achievement| achievement.grade == grade)
+            .collect::<Vec<&Achievement>>();
+        let total = achievements.len();
+        let done = achievements.iter().filter(|a| a.done).count();
+        let planned = achievements
+            .iter()
+            .filter(|a| a.present_soon && !a.done)
+            .count();
+        println!(
+            "Grade {}: {}/{} done, {} planned",
+            grade, done, total, planned
+        );
+    }
+
+    let tracker = &store.progress_tracker;
+    match tracker.grades.reached {
+        Some(grade) => println!("Grade reached: {}", grade),
+        None => println!("Grade reached: none yet"),
+    }
+    if let Some(next) = tracker.grades.next {
+        println!("Missing for grade {}:", next);
+        for missing in tracker.grades.missing.iter() {
+            println!(
+                "  {} {}/{}",
+                missing.requirement, missing.done, missing.required
+            );
+        }
+        println!("Cheapest path: {}", tracker.grades.cheapest.join(", "));
+    }
+
+    match tracker.days.iter().find(|day| day.date > Local::now()) {
+        Some(day) => {
+            let presentations = day
+                .presentations
+                .iter()
+                .map(|presentation| presentation.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            println!(
+                "Next session {} ({}): aim for {} left (optimal {}, minimum {}, current minimum {})",
+                day.date.format("%a %b %d"),
+                presentations,
+                day.target,
+                day.optimal,
+                day.minimum,
+                day.current_minimum
+            );
+            if !day.plan.is_empty() {
+                println!("Suggested: {}", day.plan.join(", "));
+            }
+        }
+        None => println!("No sessions left"),
+    }
+}
+
+fn sync(data_dir: &std::path::Path, stores: &mut [AchievementStore]) -> i32 {
+    let repo = match GitRepository::open(data_dir) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error opening git repository: {}", e);
+            return 1;
+        }
+    };
+
+    let message = commit_message::commit_message(&repo, stores);
+    let merges = match git_worker::run(&repo, GitJob::Sync, &message) {
+        Ok(GitOutcome::Done | GitOutcome::Burndown(_)) => {
+            println!("Synced");
+            return 0;
+        }
+        Ok(GitOutcome::Conflicts(merges)) => merges,
+        Err(e) => {
+            eprintln!("Error syncing with git: {}", e);
+            return 1;
+        }
+    };
+
+    if merges.iter().any(|merge| !merge.conflicts.is_empty()) {
+        for merge in merges.iter() {
+            for conflict in merge.conflicts.iter() {
+                eprintln!("Conflict in {}: {}", merge.file.display(), conflict.ours.id);
+            }
+        }
+        eprintln!("Open the window to choose between the conflicting versions");
+        let _ = repo.merge_abort();
+        return 1;
+    }
+
+    for merge in merges {
+        let file = std::fs::canonicalize(&merge.file).ok();
+        if let Some(store) = stores
+            .iter_mut()
+            .find(|store| std::fs::canonicalize(store.path.join(ACHIEVEMENTS_FILE)).ok() == file)
+        {
+            store.set_achievements(merge.resolve());
+            if let Err(e) = store.save_achievements() {
+                eprintln!("Error saving achievements: {}", e);
+                let _ = repo.merge_abort();
+                return 1;
+            }
+        }
+    }
+    match git_worker::run(&repo, GitJob::CompleteMerge { push: true }, &message) {
+        Ok(_) => {
+            println!("Synced, merging changes from both sides");
+            0
+        }
+        Err(e) => {
+            eprintln!("Error syncing with git: {}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::achievement_csv::{self, ACHIEVEMENTS_FILE};
+use crate::achievements::Achievement;
+use crate::git::Repository;
+use crate::store::AchievementStore;
+
+/// Describes how `after` differs from `before`,
+/// e.g. "Done: A3, F13; Planned: M36; Comment changed: Y60".
+///
+/// Returns an empty string if nothing changed.
+pub fn describe(before: &[Achievement], after: &[Achievement]) -> String {
+    let mut done = Vec::new();
+    let mut undone = Vec::new();
+    let mut planned = Vec::new();
+    let mut unplanned = Vec::new();
+    let mut comment = Vec::new();
+    let mut edited = Vec::new();
+    let mut added = Vec::new();
+
+    for achievement in after.iter() {
+        let id = achievement.id.clone();
+        let Some(old) = before.iter().find(|old| old.id == achievement.id) else {
+            added.push(id);
+            continue;
+        };
+        if achievement.done && !old.done {
+            done.push(id.clone());
+        }
+        if !achievement.done && old.done {
+            undone.push(id.clone());
+        }
+        if achievement.present_soon && !old.present_soon {
+            planned.push(id.clone());
+        }
+        if !achievement.present_soon && old.present_soon {
+            unplanned.push(id.clone());
+        }
+        if achievement.comment != old.comment {
+            comment.push(id.clone());
+        }
+        let mut unchanged = old.clone();
+        unchanged.done = achievement.done;
+        unchanged.present_soon = achievement.present_soon;
+        unchanged.comment = achievement.comment.clone();
+        if unchanged != *achievement {
+            edited.push(id);
+        }
+    }
+
+    let removed = before
+        .iter()
+        .filter(|old| !after.iter().any(|achievement| achievement.id == old.id))
+        .map(|old| old.id.clone())
+        .collect::<Vec<String>>();
+
+    [
+        ("Done", done),
+        ("Not done", undone),
+        ("Planned", planned),
+        ("Unplanned", unplanned),
+        ("Comment changed", comment),
+        ("Edited", edited),
+        ("Added", added),
+        ("Removed", removed),
+    ]
+    .into_iter()
+    .filter(|(_, ids)| !ids.is_empty())
+    .map(|(label, ids)| format!("{}: {}", label, ids.join(", ")))
+    .collect::<Vec<String>>()
+    .join("; ")
+}
+
+/// A commit message for the achievements of `stores` compared with the last commit.
+///
+/// Each course gets its own line, prefixed with the course name when several courses changed.
+pub fn commit_message(repo: &impl Repository, stores: &[AchievementStore]) -> String {
+    let changes = stores
+        .iter()
+        .filter_map(|store| {
+            let file = store.path.join(ACHIEVEMENTS_FILE);
+            let description = match repo.file_at("HEAD", &file) {
+                Ok(contents) => {
+                    let committed = achievement_csv::read_achievements_from_str(&contents)
+                        .map(|file| file.achievements)
+                        .unwrap_or_default();
+                    describe(&committed, &store.achievements)
+                }
+                Err(_) => format!("Add achievements for {}", store.course.name),
+            };
+            (!description.is_empty()).then(|| (store.course.name.clone(), description))
+        })
+        .collect::<Vec<(String, String)>>();
+
+    match changes.as_slice() {
+        [] => String::from("Update achievements"),
+        [(_, description)] => description.clone(),
+        _ => changes
+            .iter()
+            .map(|(course, description)| format!("{}: {}", course, description))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    }
+}
@@ -0,0 +1,437 @@
+use crate::achievements::{
+    Achievement, AchievementError, AchievementLanguage, AchievementPresention, PresentationType,
+    Sprint,
+};
+
+use crate::grades::GradeRule;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+pub const COURSE_FILE: &str = "course.toml";
+
+/// The lab calendar as edited by the user, replacing the labs in `course.toml`.
+pub const CALENDAR_FILE: &str = "calendar.toml";
+
+pub const BUNDLED_COURSE: &str = include_str!("../courses/ioopm-ht24.toml");
+
+#[derive(Debug)]
+pub enum CourseError {
+    Toml(toml::de::Error),
+    Io(std::io::Error),
+    Serialize(toml::ser::Error),
+    Date(String),
+    Achievement(AchievementError),
+}
+
+impl std::fmt::Display for CourseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CourseError::Toml(e) => write!(f, "{}", e),
+            CourseError::Io(e) => write!(f, "{}", e),
+            CourseError::Serialize(e) => write!(f, "{}", e),
+            CourseError::Date(date) => write!(f, "Invalid date {}", date),
+            CourseError::Achievement(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Parses a date written as `Sep 2, 2024`, the format used throughout the course file.
+pub fn parse_date(date: &str) -> Result<DateTime<Local>, CourseError> {
+    DateTime::parse_from_str(&format!("{date} 0:0:0 +0000"), "%b %d, %Y %H:%M:%S %z")
+        .map(|date| date.with_timezone(&Local))
+        .map_err(|_| CourseError::Date(date.to_string()))
+}
+
+/// Writes a date the way [`parse_date`] reads it, so that it reads back as the same date.
+pub fn format_date(date: DateTime<Local>) -> String {
+    date.naive_utc().format("%b %-d, %Y").to_string()
+}
+
+/// The calendar day of a date read by [`parse_date`].
+pub fn day(date: DateTime<Local>) -> NaiveDate {
+    date.naive_utc().date()
+}
+
+/// A calendar day as [`parse_date`] would read it.
+pub fn from_day(day: NaiveDate) -> DateTime<Local> {
+    day.and_time(NaiveTime::MIN).and_utc().with_timezone(&Local)
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SerializableSprint {
+    pub sprint: Sprint,
+    pub start: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SerializableLab {
+    pub date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cancelled: bool,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct SerializableCalendar {
+    pub labs: Vec<SerializableLab>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SerializableChannel {
+    pub presentation: PresentationType,
+    pub capacity: u8,
+    pub sessions: Vec<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SerializableCourseAchievement {
+    pub id: String,
+    pub title: String,
+    pub deadline: Option<String>,
+    pub grade: i8,
+    pub presenting_type: String,
+    pub programming_language: String,
+    pub sprint: Sprint,
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SerializableCourse {
+    pub name: String,
+    pub link_template: String,
+    pub labs: Vec<String>,
+    pub sprints: Vec<SerializableSprint>,
+    #[serde(default)]
+    pub channels: Vec<SerializableChannel>,
+    /// Without any, each grade needs every achievement of that grade.
+    #[serde(default)]
+    pub grades: Vec<GradeRule>,
+    pub achievements: Vec<SerializableCourseAchievement>,
+}
+
+/// A lab session. Cancelled labs stay in the calendar so they can be shown and restored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lab {
+    pub date: DateTime<Local>,
+    /// A note such as "Exam week" or "Moved from Monday".
+    pub label: Option<String>,
+    pub cancelled: bool,
+}
+
+impl Lab {
+    pub fn new(date: DateTime<Local>) -> Self {
+        Self {
+            date,
+            label: None,
+            cancelled: false,
+        }
+    }
+}
+
+/// Occasions to present achievements of one presentation type other than labs,
+/// e.g. studium sessions or report deadlines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Channel {
+    pub presentation: PresentationType,
+    /// How many achievements can be presented at each session.
+    pub capacity: u8,
+    pub sessions: Vec<DateTime<Local>>,
+}
+
+/// One occasion to present achievements.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Session {
+    pub date: DateTime<Local>,
+    pub presentation: PresentationType,
+    pub capacity: u8,
+}
+
+/// Everything that changes between course instances: the achievements,
+/// when each sprint starts, the lab sessions, what each grade takes
+/// and where achievements are described.
+#[derive(Clone, Debug)]
+pub struct Course {
+    pub name: String,
+    pub link_template: String,
+    /// Every lab including cancelled ones, in date order.
+    pub labs: Vec<Lab>,
+    pub sprints: Vec<(Sprint, DateTime<Local>)>,
+    pub channels: Vec<Channel>,
+    pub grade_rules: Vec<GradeRule>,
+    pub achievements: Vec<Achievement>,
+}
+
+impl Course {
+    /// The course definition compiled into the binary.
+    pub fn bundled() -> Self {
+        Self::from_toml(BUNDLED_COURSE).expect("the bundled course definition is valid")
+    }
+
+    /// Reads `course.toml` in `path`, falling back to the bundled course if it is missing or broken.
+    /// The labs come from `calendar.toml` instead if the user has edited them.
+    pub fn load(path: &Path) -> Self {
+        let file = path.join(COURSE_FILE);
+        let mut course = match std::fs::read_to_string(&file) {
+            Ok(contents) => match Self::from_toml(&contents) {
+                Ok(course) => course,
+                Err(e) => {
+                    eprintln!("Error reading {}: {}", file.display(), e);
+                    Self::bundled()
+                }
+            },
+            Err(_) => Self::bundled(),
+        };
+
+        let file = path.join(CALENDAR_FILE);
+        if let Ok(contents) = std::fs::read_to_string(&file) {
+            match Self::calendar_from_toml(&contents) {
+                Ok(labs) => course.labs = labs,
+                Err(e) => eprintln!("Error reading {}: {}", file.display(), e),
+            }
+        }
+        course
+    }
+
+    fn calendar_from_toml(file: &str) -> Result<Vec<Lab>, CourseError> {
+        let calendar: SerializableCalendar = toml::from_str(file).map_err(CourseError::Toml)?;
+        let mut labs = calendar
+            .labs
+            .into_iter()
+            .map(|lab| {
+                Ok(Lab {
+                    date: parse_date(&lab.date)?,
+                    label: lab.label,
+                    cancelled: lab.cancelled,
+                })
+            })
+            .collect::<Result<Vec<Lab>, CourseError>>()?;
+        labs.sort_by_key(|lab| lab.date);
+        Ok(labs)
+    }
+
+    /// Writes the labs to `calendar.toml` in `path`.
+    pub fn save_calendar(&self, path: &Path) -> Result<(), CourseError> {
+        let calendar = SerializableCalendar {
+            labs: self
+                .labs
+                .iter()
+                .map(|lab| SerializableLab {
+                    date: format_date(lab.date),
+                    label: lab.label.clone(),
+                    cancelled: lab.cancelled,
+                })
+                .collect(),
+        };
+        let contents = toml::to_string(&calendar).map_err(CourseError::Serialize)?;
+        std::fs::write(path.join(CALENDAR_FILE), contents).map_err(CourseError::Io)
+    }
+
+    /// The dates of the labs that take place.
+    pub fn lab_dates(&self) -> impl Iterator<Item = DateTime<Local>> + '_ {
+        self.labs
+            .iter()
+            .filter(|lab| !lab.cancelled)
+            .map(|lab| lab.date)
+    }
+
+    pub fn from_toml(file: &str) -> Result<Self, CourseError> {
+        let course: SerializableCourse = toml::from_str(file).map_err(CourseError::Toml)?;
+
+        let mut labs = course
+            .labs
+            .iter()
+            .map(|lab| Ok(Lab::new(parse_date(lab)?)))
+            .collect::<Result<Vec<Lab>, CourseError>>()?;
+        labs.sort_by_key(|lab| lab.date);
+
+        let sprints = course
+            .sprints
+            .into_iter()
+            .map(|sprint| Ok((sprint.sprint, parse_date(&sprint.start)?)))
+            .collect::<Result<Vec<(Sprint, DateTime<Local>)>, CourseError>>()?;
+
+        let channels = course
+            .channels
+            .into_iter()
+            .map(|channel| {
+                Ok(Channel {
+                    presentation: channel.presentation,
+                    capacity: channel.capacity,
+                    sessions: channel
+                        .sessions
+                        .iter()
+                        .map(|session| parse_date(session))
+                        .collect::<Result<Vec<DateTime<Local>>, CourseError>>()?,
+                })
+            })
+            .collect::<Result<Vec<Channel>, CourseError>>()?;
+
+        let mut achievements = Vec::new();
+        for (i, achievement) in course.achievements.into_iter().enumerate() {
+            let error = |column: &'static str, value: &str| {
+                CourseError::Achievement(AchievementError {
+                    row: i + 1,
+                    column,
+                    value: value.to_string(),
+                })
+            };
+            achievements.push(Achievement {
+                id: achievement.id,
+                title: achievement.title,
+                deadline: match achievement.deadline {
+                    Some(deadline) => Some(parse_date(&deadline)?),
+                    None => None,
+                },
+                done: false,
+                present_soon: false,
+                grade: achievement.grade,
+                presenting_type: AchievementPresention::from_string(&achievement.presenting_type)
+                    .ok_or_else(|| {
+                    error("presenting_type", &achievement.presenting_type)
+                })?,
+                programming_language: AchievementLanguage::from_string(
+                    &achievement.programming_language,
+                )
+                .ok_or_else(|| error("programming_language", &achievement.programming_language))?,
+                sprint: achievement.sprint,
+                comment: achievement.comment,
+            });
+        }
+
+        let grade_rules = match course.grades.is_empty() {
+            true => GradeRule::defaults(&achievements),
+            false => course.grades,
+        };
+
+        Ok(Course {
+            name: course.name,
+            link_template: course.link_template,
+            labs,
+            sprints,
+            channels,
+            grade_rules,
+            achievements,
+        })
+    }
+
+    /// The link to the description of an achievement.
+    ///
+    /// `{id}` in the template is replaced by the whole id (`A3`)
+    /// and `{number}` by the id without its letter (`3`).
+    pub fn link(&self, id: &str) -> String {
+        self.link_template
+            .replace("{id}", id)
+            .replace("{number}", id.get(1..).unwrap_or_default())
+    }
+
+    /// Every grade the course's achievements or grade rules mention, lowest first.
+    pub fn grades(&self) -> Vec<i8> {
+        let mut grades = self
+            .achievements
+            .iter()
+            .map(|achievement| achievement.grade)
+            .chain(self.grade_rules.iter().map(|rule| rule.grade))
+            .collect::<Vec<i8>>();
+        grades.sort();
+        grades.dedup();
+        grades
+    }
+
+    /// From the lowest to the highest of [`Course::grades`], the grades that can be aimed for.
+    pub fn grade_range(&self) -> RangeInclusive<i8> {
+        let grades = self.grades();
+        match (grades.first(), grades.last()) {
+            (Some(lowest), Some(highest)) => *lowest..=*highest,
+            _ => 3..=5,
+        }
+    }
+
+    /// The date an achievement from `sprint` can first be presented.
+    /// Sprints missing from the course file open with the last defined sprint.
+    pub fn sprint_start(&self, sprint: &Sprint) -> DateTime<Local> {
+        match self.sprints.iter().find(|(s, _)| s == sprint) {
+            Some((_, date)) => *date,
+            None => self
+                .sprints
+                .iter()
+                .map(|(_, date)| *date)
+                .max()
+                .unwrap_or_else(Local::now),
+        }
+    }
+
+    /// Every occasion to present achievements, in date order.
+    ///
+    /// Labs take `max_per_lab` achievements each. A presentation type without a channel
+    /// in the course file can be presented once per lab session.
+    pub fn sessions(&self, max_per_lab: u8) -> Vec<Session> {
+        let mut sessions = self
+            .lab_dates()
+            .map(|date| Session {
+                date,
+                presentation: PresentationType::Lab,
+                capacity: max_per_lab,
+            })
+            .collect::<Vec<Session>>();
+
+        for presentation in [
+            PresentationType::Studium,
+            PresentationType::Special,
+            PresentationType::Report,
+        ] {
+            match self
+                .channels
+                .iter()
+                .find(|channel| channel.presentation == presentation)
+            {
+                Some(channel) => sessions.extend(channel.sessions.iter().map(|date| Session {
+                    date: *date,
+                    presentation: presentation.clone(),
+                    capacity: channel.capacity,
+                })),
+                None => sessions.extend(self.lab_dates().map(|date| Session {
+                    date,
+                    presentation: presentation.clone(),
+                    capacity: 1,
+                })),
+            }
+        }
+
+        sessions.sort_by_key(|session| (session.date, session.presentation.clone()));
+        sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn edited_calendar_reads_back_the_same() {
+        let dir = TempDir::new().unwrap();
+        let mut course = Course::bundled();
+        course.labs[0].cancelled = true;
+        course.labs[1].label = Some(String::from("Exam week"));
+        course
+            .labs
+            .push(Lab::new(parse_date("Jan 9, 2025").unwrap()));
+        course.save_calendar(dir.path()).unwrap();
+
+        let loaded = Course::load(dir.path());
+        assert_eq!(loaded.labs, course.labs);
+        assert_eq!(loaded.lab_dates().count(), course.labs.len() - 1);
+    }
+
+    #[test]
+    fn grades_come_from_the_course() {
+        let mut course = Course::bundled();
+        assert_eq!(course.grades(), vec![3, 4, 5]);
+        course.achievements[0].grade = 2;
+        assert_eq!(course.grades(), vec![2, 3, 4, 5]);
+    }
+}
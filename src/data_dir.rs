@@ -0,0 +1,169 @@
+use crate::application::{Settings, SETTINGS_FILE};
+use crate::presets::PRESETS_FILE;
+use achievements_enhanced::achievement_csv::ACHIEVEMENTS_FILE;
+use achievements_enhanced::burndown::BURNDOWN_FILE;
+use achievements_enhanced::course::{CALENDAR_FILE, COURSE_FILE};
+use achievements_enhanced::progress_tracker::PINS_FILE;
+
+use std::path::{Path, PathBuf};
+
+const APP_DIR: &str = "achievements-enhanced";
+
+/// The files a course directory can hold.
+const COURSE_FILES: [&str; 6] = [
+    COURSE_FILE,
+    ACHIEVEMENTS_FILE,
+    BURNDOWN_FILE,
+    CALENDAR_FILE,
+    PINS_FILE,
+    PRESETS_FILE,
+];
+
+/// The directory holding settings.json, the course directories and the git repository.
+///
+/// `--data-dir <path>` on the command line wins, otherwise the platform's data directory
+/// is used, e.g. `~/.local/share/achievements-enhanced` following XDG on Linux.
+/// Older versions kept everything in the current directory; the first time the
+/// platform's directory is used, those files are copied over.
+pub fn data_dir(from_command_line: Option<PathBuf>) -> PathBuf {
+    let chosen = from_command_line.is_some();
+    let data_dir = from_command_line.unwrap_or_else(|| {
+        dirs::data_dir()
+            .map(|dir| dir.join(APP_DIR))
+            .unwrap_or_else(|| PathBuf::from("."))
+    });
+
+    if let Err(e) = std::fs::create_dir_all(&data_dir) {
+        eprintln!(
+            "Error creating data directory {}: {}",
+            data_dir.display(),
+            e
+        );
+    }
+    // Make it absolute so git and every file path keep working whatever the current directory is.
+    let data_dir = data_dir.canonicalize().unwrap_or(data_dir);
+
+    let old_dir = std::env::current_dir()
+        .and_then(|dir| dir.canonicalize())
+        .ok()
+        .filter(|dir| !chosen && *dir != data_dir && migrate(dir, &data_dir));
+    init_git(old_dir.as_deref(), &data_dir);
+    data_dir
+}
+
+/// Copies the settings and every course kept in `old_dir` by older versions into `data_dir`,
+/// unless `data_dir` already has settings of its own. The old files are left in place.
+/// Returns `true` if anything was copied.
+fn migrate(old_dir: &Path, data_dir: &Path) -> bool {
+    if data_dir.join(SETTINGS_FILE).exists()
+        || !(old_dir.join(SETTINGS_FILE).exists() || old_dir.join(ACHIEVEMENTS_FILE).exists())
+    {
+        return false;
+    }
+
+    let mut files = vec![PathBuf::from(SETTINGS_FILE)];
+    // Absolute course paths keep working from anywhere, only relative ones move along.
+    for course in Settings::new(old_dir).courses {
+        if course.is_relative() {
+            files.extend(COURSE_FILES.iter().map(|file| course.join(file)));
+        }
+    }
+
+    let mut copied = 0;
+    for file in files {
+        let from = old_dir.join(&file);
+        let to = data_dir.join(&file);
+        if !from.is_file() || to.exists() {
+            continue;
+        }
+        let result = std::fs::create_dir_all(to.parent().unwrap_or(data_dir))
+            .and_then(|_| std::fs::copy(&from, &to));
+        match result {
+            Ok(_) => copied += 1,
+            Err(e) => eprintln!(
+                "Error copying {} to {}: {}",
+                from.display(),
+                to.display(),
+                e
+            ),
+        }
+    }
+    if copied > 0 {
+        eprintln!(
+            "Copied {} files from {} to the data directory {}",
+            copied,
+            old_dir.display(),
+            data_dir.display()
+        );
+        // Without settings of its own the data directory would be migrated again on every start.
+        if !data_dir.join(SETTINGS_FILE).exists() {
+            Settings::new(data_dir).save();
+        }
+    }
+    copied > 0
+}
+
+/// With git sync turned on, makes sure `data_dir` is in a git repository.
+/// A new repository syncs with the remote of the repository the files were copied from
+/// out of `old_dir`, if there was one.
+fn init_git(old_dir: Option<&Path>, data_dir: &Path) {
+    if !Settings::new(data_dir).git || git2::Repository::discover(data_dir).is_ok() {
+        return;
+    }
+    let repo = match git2::Repository::init(data_dir) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!(
+                "Git sync is on, but {} is not a git repository and creating one failed: {}",
+                data_dir.display(),
+                e.message()
+            );
+            return;
+        }
+    };
+
+    let url = old_dir
+        .and_then(|dir| git2::Repository::discover(dir).ok())
+        .and_then(|old| {
+            old.find_remote("origin")
+                .ok()
+                .and_then(|remote| remote.url().map(String::from))
+        });
+    match url {
+        Some(url) => match repo.remote("origin", &url) {
+            Ok(_) => eprintln!(
+                "Created a git repository in {} syncing with {}",
+                data_dir.display(),
+                url
+            ),
+            Err(e) => eprintln!(
+                "Created a git repository in {}, but adding the remote {} failed: {}",
+                data_dir.display(),
+                url,
+                e.message()
+            ),
+        },
+        None => eprintln!(
+            "Created a git repository in {}. Add a remote called origin to sync it.",
+            data_dir.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn achievements_without_settings_are_migrated_once() {
+        let old = TempDir::new().unwrap();
+        let data = TempDir::new().unwrap();
+        std::fs::write(old.path().join(ACHIEVEMENTS_FILE), "id\n").unwrap();
+
+        assert!(migrate(old.path(), data.path()));
+        assert!(data.path().join(ACHIEVEMENTS_FILE).is_file());
+        assert!(data.path().join(SETTINGS_FILE).is_file());
+        assert!(!migrate(old.path(), data.path()));
+    }
+}
@@ -1,165 +1,665 @@
-use std::process::Command;
-
-use chrono::{DateTime, Local};
-
-pub fn git_pull() {
-    println!("Pulling changes from git");
-
-    println!("command: git pull");
-
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", "git", "pull"])
-            .output()
-            .expect("failed to execute process")
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg("git pull")
-            .output()
-            .expect("failed to execute process")
-    };
-
-    println!(
-        "output: {}",
-        String::from_utf8(output.stdout).unwrap() + &String::from_utf8(output.stderr).unwrap()
-    );
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, TimeZone};
+use git2::{
+    build::CheckoutBuilder, Cred, CredentialType, Direction, FetchOptions, IndexAddOption,
+    MergeAnalysis, Oid, PushOptions, RemoteCallbacks, Signature, Status, StatusOptions,
+};
+
+#[derive(Debug)]
+pub enum GitError {
+    Git(git2::Error),
+    /// The pull stopped because both sides changed the same lines.
+    Conflicts,
+    /// The remote refused the push, usually because it has commits we have not pulled.
+    Rejected(String),
+    /// The pull was refused because tracked files have uncommitted changes it could overwrite.
+    Dirty,
+    /// A version of a conflicted file has rows that cannot be read, so it cannot be merged row by row.
+    Unreadable(PathBuf),
 }
 
-pub fn git_add() {
-    println!("Adding changes to git");
-
-    println!("command: git add .");
-    println!("{:?}", Command::new("cmd").args(&["/C", "git", "add", "."]));
-
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", "git", "add", "."])
-            .output()
-            .expect("failed to execute process")
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg("git add .")
-            .output()
-            .expect("failed to execute process")
-    };
-
-    println!(
-        "output: {}",
-        String::from_utf8(output.stdout).unwrap() + &String::from_utf8(output.stderr).unwrap()
-    );
+impl std::fmt::Display for GitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GitError::Git(e) => write!(f, "{}", e.message()),
+            GitError::Conflicts => write!(f, "merge has conflicts"),
+            GitError::Rejected(reason) => write!(f, "push rejected: {}", reason),
+            GitError::Dirty => write!(f, "working tree has uncommitted changes"),
+            GitError::Unreadable(file) => {
+                write!(f, "{} has rows that cannot be merged", file.display())
+            }
+        }
+    }
 }
 
-pub fn git_commit() {
-    println!("Committing changes to git");
-
-    let message = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-    let command = format!("git commit -m \"{}\"", message);
-
-    println!("command: {}", command);
-
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", "git", "commit", "-m", &message])
-            .output()
-            .expect("failed to execute process")
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&command)
-            .output()
-            .expect("failed to execute process")
-    };
-
-    println!(
-        "output: {}",
-        String::from_utf8(output.stdout).unwrap() + &String::from_utf8(output.stderr).unwrap()
-    );
+impl From<git2::Error> for GitError {
+    fn from(e: git2::Error) -> Self {
+        GitError::Git(e)
+    }
 }
 
-pub fn git_push() {
-    println!("Pushing changes to git");
-
-    println!("command: git push");
-
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", "git", "push"])
-            .output()
-            .expect("failed to execute process")
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg("git push")
-            .output()
-            .expect("failed to execute process")
-    };
-
-    println!(
-        "output: {}",
-        String::from_utf8(output.stdout).unwrap() + &String::from_utf8(output.stderr).unwrap()
-    );
+/// What the app needs from version control.
+///
+/// Paths are relative to the current directory or absolute, and must lie inside the repository.
+pub trait Repository {
+    /// Stages every change in the working tree, including deleted files.
+    fn add_all(&self) -> Result<(), GitError>;
+    /// Commits whatever is staged. Does nothing if there is nothing to commit.
+    fn commit(&self, message: &str) -> Result<(), GitError>;
+    /// Fetches the upstream branch and merges it.
+    /// Fails with [`GitError::Dirty`] without touching anything if tracked files have uncommitted changes,
+    /// and with [`GitError::Conflicts`], leaving the merge in progress, if it conflicts.
+    fn pull(&self) -> Result<(), GitError>;
+    fn push(&self) -> Result<(), GitError>;
+    /// Files left with conflicts by a merge in progress, as absolute paths.
+    fn conflicted_files(&self) -> Result<Vec<PathBuf>, GitError>;
+    /// One version of a conflicted file: 1 is the common ancestor, 2 is ours and 3 is theirs.
+    fn show_stage(&self, stage: u8, file: &Path) -> Result<String, GitError>;
+    /// Gives up on a conflicted merge, restoring the files it touched to our last commit.
+    fn merge_abort(&self) -> Result<(), GitError>;
+    /// Concludes a merge once its conflicts have been resolved and staged.
+    fn commit_merge(&self) -> Result<(), GitError>;
+    /// Every commit reachable from HEAD, newest first, as short id and commit time.
+    fn commits(&self) -> Result<Vec<(String, DateTime<Local>)>, GitError>;
+    /// The contents of `file` as it was in `commit`.
+    fn file_at(&self, commit: &str, file: &Path) -> Result<String, GitError>;
 }
 
-pub fn git_get_commits() -> Vec<(String, DateTime<Local>)> {
-    println!("Getting commits from git");
-
-    println!("command: git log --format=\"%h %ai\"");
-
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", "git", "log", "--format=\"%h %ai\""])
-            .output()
-            .expect("failed to execute process")
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg("git log --format=\"%h %ai\"")
-            .output()
-            .expect("failed to execute process")
-    };
-
-    let output = String::from_utf8(output.stdout).unwrap();
-
-    let commits = output
-        .trim()
-        .split("\n")
-        .map(|s| {
-            let trimmed = s.replace("\"", "");
-            let splitted = trimmed.split_once(" ").unwrap();
-            println!("splitted: {:?}", splitted);
-            (
-                splitted.0.to_string(),
-                DateTime::parse_from_str(splitted.1, "%Y-%m-%d %H:%M:%S %z")
-                    .unwrap()
-                    .with_timezone(&Local),
-            )
+/// A repository on disk, accessed in-process through libgit2.
+pub struct GitRepository {
+    repo: git2::Repository,
+}
+
+impl GitRepository {
+    /// Opens the repository containing `path`.
+    pub fn open(path: &Path) -> Result<Self, GitError> {
+        Ok(Self {
+            repo: git2::Repository::discover(path)?,
         })
-        .collect::<Vec<(String, DateTime<Local>)>>();
+    }
+
+    fn workdir(&self) -> Result<&Path, GitError> {
+        self.repo
+            .workdir()
+            .ok_or_else(|| git2::Error::from_str("repository has no working tree").into())
+    }
+
+    /// `path` relative to the root of the working tree, as libgit2 wants it.
+    fn relative(&self, path: &Path) -> Result<PathBuf, GitError> {
+        let absolute = std::env::current_dir()
+            .map_err(|e| git2::Error::from_str(&e.to_string()))?
+            .join(path);
+        // The file itself may not exist in the working tree, only resolve its directory.
+        let directory = absolute.parent().unwrap_or(&absolute);
+        let directory = directory.canonicalize().unwrap_or(directory.to_path_buf());
+        let absolute = match absolute.file_name() {
+            Some(name) => directory.join(name),
+            None => directory,
+        };
+        let workdir = self.workdir()?;
+        let workdir = workdir.canonicalize().unwrap_or(workdir.to_path_buf());
+        absolute
+            .strip_prefix(&workdir)
+            .map(Path::to_path_buf)
+            .map_err(|_| {
+                git2::Error::from_str(&format!("{} is outside the repository", path.display()))
+                    .into()
+            })
+    }
+
+    fn signature(&self) -> Result<Signature<'static>, GitError> {
+        Ok(self
+            .repo
+            .signature()
+            .or_else(|_| Signature::now("Achievements Enhanced", "achievements@localhost"))?)
+    }
+
+    /// The branch HEAD is on, such as `refs/heads/main`, even before its first commit.
+    fn branch(&self) -> Result<String, GitError> {
+        let head = self.repo.find_reference("HEAD")?;
+        let branch = head
+            .symbolic_target()
+            .ok_or_else(|| git2::Error::from_str("HEAD is not on a branch"))?;
+        Ok(branch.to_string())
+    }
+
+    /// The remote and branch the current branch tracks. Without any configured, that is
+    /// the branch the remote's HEAD is on, or one named like ours if the remote is empty.
+    fn upstream(&self) -> Result<(String, String), GitError> {
+        let branch = self.branch()?;
+        let name = branch.strip_prefix("refs/heads/").unwrap_or(&branch);
+        let config = self.repo.config()?;
+        let remote = config
+            .get_string(&format!("branch.{}.remote", name))
+            .unwrap_or_else(|_| String::from("origin"));
+        let merge = match config.get_string(&format!("branch.{}.merge", name)) {
+            Ok(merge) => merge,
+            Err(_) => self.default_branch(&remote).unwrap_or(branch),
+        };
+        Ok((remote, merge))
+    }
+
+    /// The branch the HEAD of `remote` is on.
+    fn default_branch(&self, remote: &str) -> Result<String, GitError> {
+        let mut remote = self.repo.find_remote(remote)?;
+        let connection = remote.connect_auth(Direction::Fetch, Some(self.callbacks()), None)?;
+        let branch = connection.default_branch()?;
+        Ok(branch
+            .as_str()
+            .ok_or_else(|| git2::Error::from_str("branch name is not valid UTF-8"))?
+            .to_string())
+    }
+
+    fn callbacks(&self) -> RemoteCallbacks<'_> {
+        let config = self.repo.config().ok();
+        let attempts = Cell::new(0);
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |url, username, allowed| {
+            // libgit2 keeps asking as long as we hand out credentials, give up eventually.
+            attempts.set(attempts.get() + 1);
+            if attempts.get() > 3 {
+                return Err(git2::Error::from_str("authentication failed"));
+            }
+            if allowed.contains(CredentialType::SSH_KEY) {
+                return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+            }
+            if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                if let Some(config) = &config {
+                    return Cred::credential_helper(config, url, username);
+                }
+            }
+            Cred::default()
+        });
+        callbacks
+    }
+
+    fn commit_tree(&self, message: &str, parents: &[&git2::Commit]) -> Result<(), GitError> {
+        let mut index = self.repo.index()?;
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+        let signature = self.signature()?;
+        self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            parents,
+        )?;
+        index.write()?;
+        Ok(())
+    }
+
+    fn head_commit(&self) -> Option<git2::Commit<'_>> {
+        self.repo.head().ok()?.peel_to_commit().ok()
+    }
+
+    /// Fails with [`GitError::Dirty`] if any tracked file differs from HEAD. Untracked files are fine.
+    fn ensure_clean(&self) -> Result<(), GitError> {
+        let mut options = StatusOptions::new();
+        options.include_untracked(false).include_ignored(false);
+        let statuses = self.repo.statuses(Some(&mut options))?;
+        if statuses
+            .iter()
+            .any(|entry| entry.status() != Status::CURRENT)
+        {
+            return Err(GitError::Dirty);
+        }
+        Ok(())
+    }
+
+    /// Paths the merge in progress changed in the index, conflicted or not.
+    fn merged_paths(&self) -> Result<Vec<String>, GitError> {
+        let head = self.repo.head()?.peel_to_tree()?;
+        let index = self.repo.index()?;
+        let mut paths = Vec::new();
+        let diff = self
+            .repo
+            .diff_tree_to_index(Some(&head), Some(&index), None)?;
+        for delta in diff.deltas() {
+            for file in [delta.old_file(), delta.new_file()] {
+                if let Some(path) = file.path() {
+                    paths.push(path.to_string_lossy().to_string());
+                }
+            }
+        }
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            for entry in [conflict.ancestor, conflict.our, conflict.their]
+                .into_iter()
+                .flatten()
+            {
+                paths.push(String::from_utf8_lossy(&entry.path).to_string());
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+}
+
+impl Repository for GitRepository {
+    fn add_all(&self) -> Result<(), GitError> {
+        let mut index = self.repo.index()?;
+        index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+        index.update_all(["*"], None)?;
+        index.write()?;
+        Ok(())
+    }
+
+    fn commit(&self, message: &str) -> Result<(), GitError> {
+        let mut index = self.repo.index()?;
+        let tree = index.write_tree()?;
+        let head = self.head_commit();
+        if head.as_ref().map(|head| head.tree_id()) == Some(tree) {
+            return Ok(());
+        }
+        let parents = head.iter().collect::<Vec<&git2::Commit>>();
+        self.commit_tree(message, &parents)
+    }
+
+    fn pull(&self) -> Result<(), GitError> {
+        self.ensure_clean()?;
+        let (remote, merge) = self.upstream()?;
+        let tracking = format!(
+            "refs/remotes/{}/{}",
+            remote,
+            merge.strip_prefix("refs/heads/").unwrap_or(&merge)
+        );
+        let mut fetch_options = FetchOptions::new();
+        fetch_options.remote_callbacks(self.callbacks());
+        self.repo.find_remote(&remote)?.fetch(
+            &[&format!("+{}:{}", merge, tracking)],
+            Some(&mut fetch_options),
+            None,
+        )?;
+
+        // Nobody has pushed the branch yet, there is nothing to pull.
+        let Ok(tracking) = self.repo.find_reference(&tracking) else {
+            return Ok(());
+        };
+        let theirs = self.repo.reference_to_annotated_commit(&tracking)?;
+        let (analysis, _) = self.repo.merge_analysis(&[&theirs])?;
+
+        if analysis.contains(MergeAnalysis::ANALYSIS_UP_TO_DATE) {
+            return Ok(());
+        }
+
+        if analysis.contains(MergeAnalysis::ANALYSIS_UNBORN)
+            || analysis.contains(MergeAnalysis::ANALYSIS_FASTFORWARD)
+        {
+            // Check out first, so that a refused checkout leaves the branch where it was.
+            let target = self.repo.find_commit(theirs.id())?;
+            self.repo
+                .checkout_tree(target.as_object(), Some(CheckoutBuilder::new().safe()))?;
+            // Before our first commit, take the branch from the remote.
+            let name = match self.repo.head() {
+                Ok(head) => head.name().unwrap_or("HEAD").to_string(),
+                Err(_) => merge,
+            };
+            self.repo
+                .reference(&name, theirs.id(), true, "pull: fast-forward")?;
+            self.repo.set_head(&name)?;
+            return Ok(());
+        }
+
+        self.repo.merge(&[&theirs], None, None)?;
+        if self.repo.index()?.has_conflicts() {
+            return Err(GitError::Conflicts);
+        }
+        self.commit_merge()
+    }
+
+    fn push(&self) -> Result<(), GitError> {
+        let (remote, merge) = self.upstream()?;
+        let head = self.repo.head()?;
+        let local = head
+            .name()
+            .ok_or_else(|| git2::Error::from_str("HEAD is not on a branch"))?;
+
+        let rejected = RefCell::new(None);
+        {
+            let mut callbacks = self.callbacks();
+            callbacks.push_update_reference(|_, status| {
+                if let Some(status) = status {
+                    *rejected.borrow_mut() = Some(status.to_string());
+                }
+                Ok(())
+            });
+            let mut push_options = PushOptions::new();
+            push_options.remote_callbacks(callbacks);
+            self.repo
+                .find_remote(&remote)?
+                .push(&[&format!("{}:{}", local, merge)], Some(&mut push_options))?;
+        }
+
+        match rejected.into_inner() {
+            Some(reason) => Err(GitError::Rejected(reason)),
+            None => Ok(()),
+        }
+    }
+
+    fn conflicted_files(&self) -> Result<Vec<PathBuf>, GitError> {
+        let workdir = self.workdir()?;
+        let index = self.repo.index()?;
+        let mut files = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+            if let Some(entry) = entry {
+                let file = workdir.join(String::from_utf8_lossy(&entry.path).as_ref());
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    fn show_stage(&self, stage: u8, file: &Path) -> Result<String, GitError> {
+        let path = self.relative(file)?;
+        let index = self.repo.index()?;
+        let entry = index.get_path(&path, stage as i32).ok_or_else(|| {
+            git2::Error::from_str(&format!("{} has no stage {}", path.display(), stage))
+        })?;
+        let blob = self.repo.find_blob(entry.id)?;
+        Ok(String::from_utf8_lossy(blob.content()).to_string())
+    }
+
+    fn merge_abort(&self) -> Result<(), GitError> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        let paths = self.merged_paths()?;
+        if !paths.is_empty() {
+            self.repo
+                .reset_default(Some(head.as_object()), paths.iter())?;
+            // Files the merge added are untracked after the reset and have to go as well.
+            let mut checkout = CheckoutBuilder::new();
+            checkout.force().remove_untracked(true);
+            for path in &paths {
+                checkout.path(path);
+            }
+            self.repo.checkout_head(Some(&mut checkout))?;
+        }
+        self.repo.cleanup_state()?;
+        Ok(())
+    }
+
+    fn commit_merge(&self) -> Result<(), GitError> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        // Read MERGE_HEAD directly, mergehead_foreach wants a mutable repository.
+        let theirs = std::fs::read_to_string(self.repo.path().join("MERGE_HEAD"))
+            .unwrap_or_default()
+            .lines()
+            .map(|id| self.repo.find_commit(Oid::from_str(id.trim())?))
+            .collect::<Result<Vec<git2::Commit>, git2::Error>>()?;
+
+        let mut parents = vec![&head];
+        parents.extend(theirs.iter());
+        let message = self
+            .repo
+            .message()
+            .unwrap_or_else(|_| String::from("Merge remote changes"));
+        self.commit_tree(message.trim(), &parents)?;
+        self.repo.cleanup_state()?;
+        Ok(())
+    }
+
+    fn commits(&self) -> Result<Vec<(String, DateTime<Local>)>, GitError> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TIME)?;
+
+        let mut commits = Vec::new();
+        for id in revwalk {
+            let commit = self.repo.find_commit(id?)?;
+            let Some(date) = Local.timestamp_opt(commit.time().seconds(), 0).single() else {
+                continue;
+            };
+            let short = commit.as_object().short_id()?;
+            commits.push((short.as_str().unwrap_or_default().to_string(), date));
+        }
+        Ok(commits)
+    }
 
-    commits
+    fn file_at(&self, commit: &str, file: &Path) -> Result<String, GitError> {
+        let path = self.relative(file)?;
+        let tree = self.repo.revparse_single(commit)?.peel_to_tree()?;
+        let blob = tree
+            .get_path(&path)?
+            .to_object(&self.repo)?
+            .peel_to_blob()?;
+        Ok(String::from_utf8_lossy(blob.content()).to_string())
+    }
 }
 
-pub fn git_get_achievements_from_commit(commit: String) -> String {
-    println!("Getting achievements from commit {}", commit);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FILE: &str = "achievements.csv";
+
+    /// A bare remote with one clone that has pushed `contents` as its first commit.
+    fn remote(contents: &str) -> (TempDir, GitRepository) {
+        let dir = TempDir::new().unwrap();
+        git2::Repository::init_bare(dir.path().join("remote.git")).unwrap();
+        let repo = clone(&dir, "first");
+        write(&repo, contents);
+        repo.add_all().unwrap();
+        repo.commit("first").unwrap();
+        repo.push().unwrap();
+        (dir, repo)
+    }
+
+    fn clone(dir: &TempDir, name: &str) -> GitRepository {
+        let repo = git2::Repository::clone(
+            dir.path().join("remote.git").to_str().unwrap(),
+            dir.path().join(name),
+        )
+        .unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", name).unwrap();
+        config.set_str("user.email", "test@localhost").unwrap();
+        GitRepository { repo }
+    }
+
+    fn path(repo: &GitRepository) -> PathBuf {
+        repo.workdir().unwrap().join(FILE)
+    }
+
+    fn write(repo: &GitRepository, contents: &str) {
+        std::fs::write(path(repo), contents).unwrap();
+    }
+
+    fn read(repo: &GitRepository) -> String {
+        std::fs::read_to_string(path(repo)).unwrap()
+    }
+
+    #[test]
+    fn pull_brings_in_pushed_commits() {
+        let (dir, first) = remote("a\n");
+        let second = clone(&dir, "second");
+
+        write(&first, "b\n");
+        first.add_all().unwrap();
+        first.commit("second").unwrap();
+        first.push().unwrap();
+
+        second.pull().unwrap();
+        assert_eq!(read(&second), "b\n");
+        assert_eq!(second.commits().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn pull_from_an_empty_remote_does_nothing() {
+        let dir = TempDir::new().unwrap();
+        git2::Repository::init_bare(dir.path().join("remote.git")).unwrap();
+        let repo = clone(&dir, "first");
+        write(&repo, "a\n");
+        repo.add_all().unwrap();
+        repo.commit("first").unwrap();
+
+        repo.pull().unwrap();
+        repo.push().unwrap();
+        assert_eq!(read(&clone(&dir, "second")), "a\n");
+    }
+
+    #[test]
+    fn pull_into_a_repository_without_commits_takes_the_remote_branch() {
+        let (dir, _first) = remote("a\n");
+        let repo = git2::Repository::init(dir.path().join("second")).unwrap();
+        repo.remote("origin", dir.path().join("remote.git").to_str().unwrap())
+            .unwrap();
+        repo.set_head("refs/heads/trunk").unwrap();
+        let second = GitRepository { repo };
+
+        second.pull().unwrap();
+        assert_eq!(read(&second), "a\n");
+        let remote_branch = git2::Repository::open(dir.path().join("remote.git"))
+            .unwrap()
+            .head()
+            .unwrap()
+            .name()
+            .unwrap()
+            .to_string();
+        assert_eq!(second.branch().unwrap(), remote_branch);
+    }
+
+    #[test]
+    fn history_can_be_read_back() {
+        let (_dir, repo) = remote("a\n");
+        write(&repo, "b\n");
+        repo.add_all().unwrap();
+        repo.commit("second").unwrap();
+
+        let commits = repo.commits().unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(repo.file_at(&commits[0].0, &path(&repo)).unwrap(), "b\n");
+        assert_eq!(repo.file_at(&commits[1].0, &path(&repo)).unwrap(), "a\n");
+    }
+
+    #[test]
+    fn commit_without_changes_does_nothing() {
+        let (_dir, repo) = remote("a\n");
+        repo.add_all().unwrap();
+        repo.commit("nothing").unwrap();
+        assert_eq!(repo.commits().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn push_is_rejected_when_behind() {
+        let (dir, first) = remote("a\n");
+        let second = clone(&dir, "second");
+
+        write(&first, "b\n");
+        first.add_all().unwrap();
+        first.commit("first side").unwrap();
+        first.push().unwrap();
+
+        std::fs::write(second.workdir().unwrap().join("other"), "c\n").unwrap();
+        second.add_all().unwrap();
+        second.commit("second side").unwrap();
+        assert!(second.push().is_err());
+
+        second.pull().unwrap();
+        second.push().unwrap();
+        assert_eq!(read(&second), "b\n");
+    }
+
+    /// Two clones that changed the only line of the file differently, the second one pulling.
+    fn conflict() -> (TempDir, GitRepository) {
+        let (dir, first) = remote("a\n");
+        let second = clone(&dir, "second");
+
+        write(&first, "b\n");
+        first.add_all().unwrap();
+        first.commit("theirs").unwrap();
+        first.push().unwrap();
+
+        write(&second, "c\n");
+        second.add_all().unwrap();
+        second.commit("ours").unwrap();
+        assert!(matches!(second.pull(), Err(GitError::Conflicts)));
+        (dir, second)
+    }
+
+    #[test]
+    fn conflicts_can_be_resolved() {
+        let (_dir, repo) = conflict();
+
+        let files = repo.conflicted_files().unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].file_name().unwrap(), FILE);
+        assert_eq!(repo.show_stage(1, &files[0]).unwrap(), "a\n");
+        assert_eq!(repo.show_stage(2, &files[0]).unwrap(), "c\n");
+        assert_eq!(repo.show_stage(3, &files[0]).unwrap(), "b\n");
+
+        write(&repo, "b\nc\n");
+        repo.add_all().unwrap();
+        repo.commit_merge().unwrap();
+        repo.push().unwrap();
+
+        assert!(repo.conflicted_files().unwrap().is_empty());
+        let head = repo.repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+        assert_eq!(read(&repo), "b\nc\n");
+    }
+
+    #[test]
+    fn aborted_merge_keeps_our_version() {
+        let (_dir, repo) = conflict();
+
+        repo.merge_abort().unwrap();
+        assert!(repo.conflicted_files().unwrap().is_empty());
+        assert_eq!(read(&repo), "c\n");
+        assert_eq!(repo.repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn pull_refuses_to_overwrite_uncommitted_changes() {
+        let (dir, first) = remote("a\n");
+        let second = clone(&dir, "second");
+
+        write(&first, "b\n");
+        first.add_all().unwrap();
+        first.commit("theirs").unwrap();
+        first.push().unwrap();
+
+        write(&second, "c\n");
+        assert!(matches!(second.pull(), Err(GitError::Dirty)));
+        assert_eq!(read(&second), "c\n");
+        assert_eq!(second.commits().unwrap().len(), 1);
+    }
 
-    println!("command: git show {}:achievements.csv", commit);
+    #[test]
+    fn aborted_merge_leaves_other_files_alone() {
+        let (dir, first) = remote("a\n");
+        let second = clone(&dir, "second");
+        let notes = second.workdir().unwrap().join("notes");
+        std::fs::write(&notes, "old\n").unwrap();
+        second.add_all().unwrap();
+        second.commit("notes").unwrap();
 
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", "git", "show", &format!("{}:achievements.csv", commit)])
-            .output()
-            .expect("failed to execute process")
-    } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&format!("git show {}:achievements.csv", commit))
-            .output()
-            .expect("failed to execute process")
-    };
+        write(&first, "b\n");
+        std::fs::write(first.workdir().unwrap().join("added"), "new\n").unwrap();
+        first.add_all().unwrap();
+        first.commit("theirs").unwrap();
+        first.push().unwrap();
 
-    let output = String::from_utf8(output.stdout).unwrap();
+        write(&second, "c\n");
+        second.add_all().unwrap();
+        second.commit("ours").unwrap();
+        assert!(matches!(second.pull(), Err(GitError::Conflicts)));
 
-    output
+        std::fs::write(&notes, "edited during the merge\n").unwrap();
+        second.merge_abort().unwrap();
+        assert_eq!(read(&second), "c\n");
+        assert!(!second.workdir().unwrap().join("added").exists());
+        assert_eq!(
+            std::fs::read_to_string(&notes).unwrap(),
+            "edited during the merge\n"
+        );
+        assert_eq!(second.repo.state(), git2::RepositoryState::Clean);
+    }
 }
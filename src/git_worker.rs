@@ -0,0 +1,163 @@
+use achievements_enhanced::achievement_csv::ACHIEVEMENTS_FILE;
+use achievements_enhanced::burndown::{self, BurndownPoint};
+use achievements_enhanced::git::{GitError, GitRepository, Repository};
+use achievements_enhanced::merge::PendingMerge;
+
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use chrono::{DateTime, Local};
+use eframe::egui;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GitJob {
+    /// Commit local changes and pull.
+    Pull,
+    /// Commit local changes, pull and push.
+    Sync,
+    /// Commit a merge whose conflicts the user resolved, then push if the pull was part of a sync.
+    CompleteMerge { push: bool },
+    /// Give up on a conflicted merge and go back to our last commit.
+    AbortMerge,
+    /// Read the burndown of the course in `path` from the history of the repository holding it.
+    Burndown { path: PathBuf },
+}
+
+pub enum GitOutcome {
+    Done,
+    /// The pull stopped on conflicting achievements files that have to be merged before committing.
+    Conflicts(Vec<PendingMerge>),
+    Burndown(Vec<BurndownPoint>),
+}
+
+pub struct GitEvent {
+    pub job: GitJob,
+    pub result: Result<GitOutcome, GitError>,
+}
+
+/// What the UI shows about git synchronization.
+#[derive(Default)]
+pub struct SyncStatus {
+    pub running: Option<GitJob>,
+    pub last_sync: Option<DateTime<Local>>,
+    pub error: Option<String>,
+}
+
+/// Runs git on a background thread so a slow remote never freezes the window.
+///
+/// Local changes are always committed before pulling,
+/// so a failed pull or push leaves them safe in a local commit.
+pub struct GitWorker {
+    /// Jobs and the message to commit local changes with.
+    jobs: Sender<(GitJob, String)>,
+    events: Receiver<GitEvent>,
+}
+
+impl GitWorker {
+    /// Starts the worker for the repository containing `data_dir`.
+    pub fn new(ctx: egui::Context, data_dir: PathBuf) -> Self {
+        let (jobs, job_receiver) = mpsc::channel::<(GitJob, String)>();
+        let (event_sender, events) = mpsc::channel::<GitEvent>();
+
+        thread::spawn(move || {
+            for (job, message) in job_receiver {
+                let dir = match &job {
+                    GitJob::Burndown { path } => path,
+                    _ => &data_dir,
+                };
+                let result =
+                    GitRepository::open(dir).and_then(|repo| run(&repo, job.clone(), &message));
+                if event_sender.send(GitEvent { job, result }).is_err() {
+                    break;
+                }
+                ctx.request_repaint();
+            }
+        });
+
+        Self { jobs, events }
+    }
+
+    pub fn start(&self, job: GitJob, message: String) {
+        let _ = self.jobs.send((job, message));
+    }
+
+    pub fn poll(&self) -> Option<GitEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+/// Runs `job` to completion on the calling thread.
+pub fn run(repo: &impl Repository, job: GitJob, message: &str) -> Result<GitOutcome, GitError> {
+    match job {
+        GitJob::Pull | GitJob::Sync => {
+            repo.add_all()?;
+            repo.commit(message)?;
+            match repo.pull() {
+                Ok(()) => {}
+                Err(GitError::Conflicts) => return conflicts(repo),
+                Err(e) => return Err(e),
+            }
+            if job == GitJob::Sync {
+                repo.push()?;
+            }
+        }
+        GitJob::CompleteMerge { push } => {
+            if has_conflict_markers(repo)? {
+                // Something was left unresolved, committing would put the markers into the history.
+                let _ = repo.merge_abort();
+                return Err(GitError::Conflicts);
+            }
+            repo.add_all()?;
+            repo.commit_merge()?;
+            if push {
+                repo.push()?;
+            }
+        }
+        GitJob::AbortMerge => {
+            repo.merge_abort()?;
+        }
+        GitJob::Burndown { path } => {
+            return Ok(GitOutcome::Burndown(burndown::history(repo, &path)?));
+        }
+    }
+    Ok(GitOutcome::Done)
+}
+
+/// Merges the achievements files left conflicted by a pull.
+///
+/// Aborts the merge and fails if files other than achievements files conflict.
+fn conflicts(repo: &impl Repository) -> Result<GitOutcome, GitError> {
+    let merges = repo.conflicted_files().and_then(|files| {
+        if !files
+            .iter()
+            .all(|file| file.file_name() == Some(ACHIEVEMENTS_FILE.as_ref()))
+        {
+            return Err(GitError::Conflicts);
+        }
+        files
+            .iter()
+            .map(|file| PendingMerge::from_git(repo, file))
+            .collect::<Result<Vec<PendingMerge>, GitError>>()
+    });
+
+    match merges {
+        Ok(merges) => Ok(GitOutcome::Conflicts(merges)),
+        Err(e) => {
+            // Leave the working tree as it was before the pull so no local edits are lost.
+            let _ = repo.merge_abort();
+            Err(e)
+        }
+    }
+}
+
+/// Whether any file still conflicted in the index has conflict markers in the working tree.
+fn has_conflict_markers(repo: &impl Repository) -> Result<bool, GitError> {
+    Ok(repo.conflicted_files()?.iter().any(|file| {
+        std::fs::read_to_string(file).is_ok_and(|contents| {
+            contents
+                .lines()
+                .any(|line| line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>"))
+        })
+    }))
+}
@@ -0,0 +1,300 @@
+use crate::achievements::{Achievement, Sprint};
+
+/// A set of achievements of which some number must be done.
+///
+/// An achievement belongs to the set if it matches every filter that is not empty.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Requirement {
+    /// Shown instead of a description made from the filters.
+    pub name: Option<String>,
+    #[serde(default)]
+    pub grades: Vec<i8>,
+    #[serde(default)]
+    pub sprints: Vec<Sprint>,
+    #[serde(default)]
+    pub ids: Vec<String>,
+    /// How many must be done. All of them if missing.
+    pub count: Option<usize>,
+}
+
+impl std::fmt::Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            return write!(f, "{}", name);
+        }
+        let mut filters = Vec::new();
+        if !self.grades.is_empty() {
+            filters.push(format!(
+                "grade {}",
+                self.grades
+                    .iter()
+                    .map(|grade| grade.to_string())
+                    .collect::<Vec<String>>()
+                    .join("/")
+            ));
+        }
+        if !self.sprints.is_empty() {
+            filters.push(
+                self.sprints
+                    .iter()
+                    .map(|sprint| format!("{:?}", sprint))
+                    .collect::<Vec<String>>()
+                    .join("/"),
+            );
+        }
+        if !self.ids.is_empty() {
+            filters.push(self.ids.join(", "));
+        }
+        match filters.is_empty() {
+            true => write!(f, "any"),
+            false => write!(f, "{}", filters.join(" ")),
+        }
+    }
+}
+
+impl Requirement {
+    pub fn matches(&self, achievement: &Achievement) -> bool {
+        (self.grades.is_empty() || self.grades.contains(&achievement.grade))
+            && (self.sprints.is_empty() || self.sprints.contains(&achievement.sprint))
+            && (self.ids.is_empty()
+                || self
+                    .ids
+                    .iter()
+                    .any(|id| id.eq_ignore_ascii_case(&achievement.id)))
+    }
+
+    /// How many matching achievements must be done.
+    pub fn required(&self, achievements: &[Achievement]) -> usize {
+        match self.count {
+            Some(count) => count,
+            None => achievements.iter().filter(|a| self.matches(a)).count(),
+        }
+    }
+}
+
+/// What it takes to get a grade, on top of the rules for every lower grade.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GradeRule {
+    pub grade: i8,
+    pub requirements: Vec<Requirement>,
+}
+
+impl GradeRule {
+    /// The rules for a course file without any: every achievement of a grade is needed for it.
+    pub fn defaults(achievements: &[Achievement]) -> Vec<GradeRule> {
+        let mut grades = achievements
+            .iter()
+            .map(|achievement| achievement.grade)
+            .collect::<Vec<i8>>();
+        grades.sort();
+        grades.dedup();
+        grades
+            .into_iter()
+            .map(|grade| GradeRule {
+                grade,
+                requirements: vec![Requirement {
+                    name: None,
+                    grades: vec![grade],
+                    sprints: Vec::new(),
+                    ids: Vec::new(),
+                    count: None,
+                }],
+            })
+            .collect()
+    }
+}
+
+/// A requirement of a grade not reached yet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Missing {
+    pub requirement: Requirement,
+    pub done: usize,
+    pub required: usize,
+}
+
+/// Where the achievements stand against the grade rules.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GradeStatus {
+    /// The highest grade whose rules and those of every lower grade are met.
+    pub reached: Option<i8>,
+    /// The grade after `reached`, if there is one.
+    pub next: Option<i8>,
+    /// The requirements of `next` that are not met yet.
+    pub missing: Vec<Missing>,
+    /// Ids of the fewest achievements to do to reach `next`, in the order to do them.
+    pub cheapest: Vec<String>,
+}
+
+/// Checks `achievements` against `rules`.
+pub fn evaluate(rules: &[GradeRule], achievements: &[Achievement]) -> GradeStatus {
+    let mut rules = rules.iter().collect::<Vec<&GradeRule>>();
+    rules.sort_by_key(|rule| rule.grade);
+
+    let mut status = GradeStatus::default();
+    for rule in rules.iter() {
+        let missing = rule
+            .requirements
+            .iter()
+            .map(|requirement| Missing {
+                requirement: requirement.clone(),
+                done: achievements
+                    .iter()
+                    .filter(|a| a.done && requirement.matches(a))
+                    .count(),
+                required: requirement.required(achievements),
+            })
+            .filter(|missing| missing.done < missing.required)
+            .collect::<Vec<Missing>>();
+
+        if !missing.is_empty() {
+            status.next = Some(rule.grade);
+            status.missing = missing;
+            break;
+        }
+        status.reached = Some(rule.grade);
+    }
+
+    status.cheapest = cheapest(&status.missing, achievements);
+    status
+}
+
+/// Picks achievements that are not done until every requirement in `missing` is met,
+/// each time the one counting towards the most requirements still open.
+///
+/// Ties go to achievements marked `present_soon`, then earlier sprints, then file order.
+/// Requirements that cannot be met with the achievements there are get all that match.
+fn cheapest(missing: &[Missing], achievements: &[Achievement]) -> Vec<String> {
+    let mut open = missing
+        .iter()
+        .map(|missing| missing.required.saturating_sub(missing.done))
+        .collect::<Vec<usize>>();
+    let mut left = achievements
+        .iter()
+        .filter(|achievement| !achievement.done)
+        .collect::<Vec<&Achievement>>();
+    let mut path = Vec::new();
+
+    loop {
+        let counts = |achievement: &Achievement| {
+            missing
+                .iter()
+                .zip(open.iter())
+                .filter(|(missing, open)| **open > 0 && missing.requirement.matches(achievement))
+                .count()
+        };
+        let best = left
+            .iter()
+            .enumerate()
+            .filter(|(_, achievement)| counts(achievement) > 0)
+            .min_by_key(|(i, achievement)| {
+                (
+                    std::cmp::Reverse(counts(achievement)),
+                    !achievement.present_soon,
+                    achievement.sprint.clone(),
+                    *i,
+                )
+            })
+            .map(|(i, _)| i);
+        let Some(best) = best else {
+            break;
+        };
+
+        let achievement = left.remove(best);
+        for (missing, open) in missing.iter().zip(open.iter_mut()) {
+            if missing.requirement.matches(achievement) {
+                *open = open.saturating_sub(1);
+            }
+        }
+        path.push(achievement.id.clone());
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::{
+        AchievementLanguage, AchievementPresention, PresentationType, ProgrammingLanguage,
+    };
+
+    fn achievement(id: &str, grade: i8, sprint: Sprint, done: bool) -> Achievement {
+        Achievement {
+            id: id.to_string(),
+            title: String::new(),
+            deadline: None,
+            done,
+            present_soon: false,
+            grade,
+            presenting_type: AchievementPresention::Single(PresentationType::Lab),
+            programming_language: AchievementLanguage::Single(ProgrammingLanguage::C),
+            sprint,
+            comment: None,
+        }
+    }
+
+    fn requirement(grades: &[i8], sprints: &[Sprint], count: Option<usize>) -> Requirement {
+        Requirement {
+            name: None,
+            grades: grades.to_vec(),
+            sprints: sprints.to_vec(),
+            ids: Vec::new(),
+            count,
+        }
+    }
+
+    #[test]
+    fn defaults_need_every_achievement_up_to_the_grade() {
+        let achievements = [
+            achievement("A1", 3, Sprint::Sprint1, true),
+            achievement("A2", 3, Sprint::Sprint1, true),
+            achievement("A3", 4, Sprint::Sprint1, false),
+            achievement("A4", 5, Sprint::Sprint1, true),
+        ];
+        let status = evaluate(&GradeRule::defaults(&achievements), &achievements);
+        assert_eq!(status.reached, Some(3));
+        assert_eq!(status.next, Some(4));
+        assert_eq!(status.missing.len(), 1);
+        assert_eq!(status.cheapest, ["A3"]);
+    }
+
+    #[test]
+    fn cheapest_path_prefers_achievements_counting_twice() {
+        let achievements = [
+            achievement("A1", 3, Sprint::Sprint1, true),
+            achievement("A2", 4, Sprint::Sprint1, false),
+            achievement("A3", 4, Sprint::Sprint2, false),
+            achievement("A4", 4, Sprint::Project, false),
+            achievement("A5", 3, Sprint::Project, false),
+        ];
+        let rules = [
+            GradeRule {
+                grade: 3,
+                requirements: vec![requirement(&[3], &[Sprint::Sprint1], None)],
+            },
+            GradeRule {
+                grade: 4,
+                requirements: vec![
+                    requirement(&[4], &[], Some(2)),
+                    requirement(&[], &[Sprint::Project], Some(1)),
+                ],
+            },
+        ];
+
+        let status = evaluate(&rules, &achievements);
+        assert_eq!(status.reached, Some(3));
+        assert_eq!(status.next, Some(4));
+        assert_eq!(status.missing.len(), 2);
+        assert_eq!(status.cheapest, ["A4", "A2"]);
+    }
+
+    #[test]
+    fn nothing_is_missing_once_the_top_grade_is_reached() {
+        let achievements = [achievement("A1", 3, Sprint::Sprint1, true)];
+        let status = evaluate(&GradeRule::defaults(&achievements), &achievements);
+        assert_eq!(status.reached, Some(3));
+        assert_eq!(status.next, None);
+        assert!(status.missing.is_empty());
+        assert!(status.cheapest.is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+use crate::achievement_csv::BrokenRow;
+use crate::achievements::Achievement;
+
+/// How many commands are kept before the oldest ones are forgotten.
+const LIMIT: usize = 100;
+
+/// The achievements and broken rows of a course at one point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub achievements: Vec<Achievement>,
+    pub broken_rows: Vec<BrokenRow>,
+}
+
+/// One change to a course's achievements, as they were before and after it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Achievements changed in place, as (before, after) pairs matched by id.
+    Edit(Vec<(Achievement, Achievement)>),
+    /// Every achievement replaced at once, e.g. by a merge or a reset.
+    Replace { before: Snapshot, after: Snapshot },
+}
+
+impl Command {
+    /// The command that takes back this one.
+    pub fn reversed(self) -> Command {
+        match self {
+            Command::Edit(changes) => Command::Edit(
+                changes
+                    .into_iter()
+                    .map(|(before, after)| (after, before))
+                    .collect(),
+            ),
+            Command::Replace { before, after } => Command::Replace {
+                before: after,
+                after: before,
+            },
+        }
+    }
+}
+
+/// The changes made to a course that can be undone and redone.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    undo: Vec<Command>,
+    redo: Vec<Command>,
+}
+
+impl History {
+    /// Remembers a change that was just made. Anything undone before can no longer be redone.
+    pub fn record(&mut self, command: Command) {
+        self.undo.push(command);
+        if self.undo.len() > LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// The command that takes back the last change, if there is one.
+    pub fn undo(&mut self) -> Option<Command> {
+        let command = self.undo.pop()?;
+        self.redo.push(command.clone());
+        Some(command.reversed())
+    }
+
+    /// The command that makes the last undone change again, if there is one.
+    pub fn redo(&mut self) -> Option<Command> {
+        let command = self.redo.pop()?;
+        self.undo.push(command.clone());
+        Some(command)
+    }
+
+    /// Forgets every change, e.g. once they no longer apply to what is on disk.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::achievement_csv::{ACHIEVEMENTS_FILE, COLUMNS};
+    use crate::store::AchievementStore;
+    use tempfile::TempDir;
+
+    #[test]
+    fn undo_and_redo_walk_back_and_forth_through_edits() {
+        let dir = TempDir::new().unwrap();
+        let mut store = AchievementStore::open(dir.path());
+        let original = store.achievements.clone();
+        let id = original[0].id.clone();
+
+        store.set_done(&id, true);
+        store.edit_all(|achievement| achievement.present_soon = true);
+        let edited = store.achievements.clone();
+
+        assert!(store.undo());
+        assert!(store.get(&id).unwrap().done);
+        assert!(!store.get(&id).unwrap().present_soon);
+        assert!(store.undo());
+        assert_eq!(store.achievements, original);
+        assert!(!store.undo());
+
+        assert!(store.redo());
+        assert!(store.redo());
+        assert_eq!(store.achievements, edited);
+        assert!(!store.redo());
+
+        store.reset_to_defaults();
+        assert!(store.undo());
+        assert_eq!(store.achievements, edited);
+        store.set_done(&id, false);
+        assert!(!store.redo());
+    }
+
+    #[test]
+    fn undoing_a_replace_keeps_the_current_order() {
+        let dir = TempDir::new().unwrap();
+        let mut store = AchievementStore::open(dir.path());
+        let id = store.achievements[0].id.clone();
+        store.set_done(&id, true);
+
+        store.reset_to_defaults();
+        store.achievements.reverse();
+        let order = store
+            .achievements
+            .iter()
+            .map(|achievement| achievement.id.clone())
+            .collect::<Vec<String>>();
+
+        assert!(store.undo());
+        assert!(store.get(&id).unwrap().done);
+        let restored = store
+            .achievements
+            .iter()
+            .map(|achievement| achievement.id.clone())
+            .collect::<Vec<String>>();
+        assert_eq!(restored, order);
+    }
+
+    #[test]
+    fn reload_forgets_the_history() {
+        let dir = TempDir::new().unwrap();
+        let mut store = AchievementStore::open(dir.path());
+        store.save_achievements().unwrap();
+        let snapshot = store.achievements.clone();
+        let id = snapshot[0].id.clone();
+
+        store.set_done(&id, true);
+        assert!(store.undo());
+        store.reload(&snapshot);
+        assert!(!store.history.can_undo());
+        assert!(!store.history.can_redo());
+    }
+
+    #[test]
+    fn skipping_all_broken_rows_can_be_undone() {
+        let dir = TempDir::new().unwrap();
+        let contents = format!(
+            "{}\nA1,,Title,,false,false,five,Lab,C,Sprint1,\n",
+            COLUMNS.join(",")
+        );
+        std::fs::write(dir.path().join(ACHIEVEMENTS_FILE), contents).unwrap();
+        let mut store = AchievementStore::open(dir.path());
+        assert_eq!(store.broken_rows.len(), 1);
+
+        store.skip_all_broken_rows();
+        assert!(store.broken_rows.is_empty());
+        assert!(store.undo());
+        assert_eq!(store.broken_rows.len(), 1);
+    }
+}
@@ -27,15 +27,29 @@ pub struct Language {
     pub sprint: String,
     pub comment: String,
     pub click_to_sort: String,
+    pub shift_click_to_sort_too: String,
     pub right_click_to_filter: String,
     pub no_specific_language: String,
+    pub set_deadline: String,
+    pub clear_deadline: String,
 
     pub settings: String,
     pub file: String,
     pub edit: String,
     pub clear_done: String,
     pub clear_present_soon: String,
+    pub undo: String,
+    pub redo: String,
+    pub confirm_clear_done: String,
+    pub confirm_clear_present_soon: String,
     pub clear_filters: String,
+    pub search: String,
+    pub search_help: String,
+    pub presets: String,
+    pub preset_name: String,
+    pub save_preset: String,
+    pub save_preset_hint: String,
+    pub no_shortcut: String,
     pub save: String,
 
     pub date: String,
@@ -44,10 +58,17 @@ pub struct Language {
     pub target: String,
     pub minimum_to_reach_target_grade: String,
     pub click_to_hide_passed_labs: String,
+    pub plan: String,
+    pub click_to_pin: String,
+    pub click_to_unpin: String,
+    pub pin_achievement: String,
     pub click_to_show_passed_labs: String,
     pub click_to_show_achievements_done: String,
     pub click_to_show_achievements_left: String,
     pub lab: String,
+    pub studium: String,
+    pub special: String,
+    pub report: String,
     pub grade3: String,
     pub grade4: String,
     pub grade5: String,
@@ -58,6 +79,19 @@ pub struct Language {
     pub project: String,
     pub other: String,
     pub total: String,
+    pub grade_reached: String,
+    pub no_grade_yet: String,
+    pub missing_for_grade: String,
+    pub cheapest_path: String,
+    pub what_if: String,
+    pub what_if_hint: String,
+    pub reset: String,
+    pub labs: String,
+    pub edit_labs: String,
+    pub add_lab: String,
+    pub cancelled: String,
+    pub label: String,
+    pub lab_date_hint: String,
 
     pub font_size: String,
     pub dark_mode: String,
@@ -67,6 +101,54 @@ pub struct Language {
     pub git: String,
     pub burndown: String,
     pub generate: String,
+    pub burndown_chart: String,
+    pub remaining: String,
+    pub remaining_after_planned: String,
+    pub ideal: String,
+    pub refresh: String,
+    pub no_history: String,
+    pub course: String,
+    pub courses: String,
+    pub data_dir: String,
+    pub data_dir_hint: String,
+    pub add_course: String,
+    pub remove: String,
+
+    pub broken_rows: String,
+    pub broken_rows_description: String,
+    pub unreadable_file: String,
+    pub unreadable_file_description: String,
+    pub read_again: String,
+    pub row: String,
+    pub invalid_value: String,
+    pub fix: String,
+    pub skip: String,
+    pub use_default: String,
+    pub skip_all: String,
+    pub reset_to_defaults: String,
+
+    pub sync: String,
+    pub syncing: String,
+    pub syncing_before_closing: String,
+    pub last_sync: String,
+    pub sync_failed: String,
+    pub changes_kept_locally: String,
+    pub retry: String,
+    pub quit_anyway: String,
+    pub cancel: String,
+    pub commit_message: String,
+    pub commit_and_sync: String,
+    pub edit_commit_message: String,
+
+    pub merge_conflicts: String,
+    pub merge_conflicts_description: String,
+    pub field: String,
+    pub mine: String,
+    pub theirs: String,
+    pub keep_all_mine: String,
+    pub take_all_theirs: String,
+    pub apply_merge: String,
+    pub cancel_merge: String,
 }
 
 pub fn get_english() -> Language {
@@ -82,17 +164,31 @@ pub fn get_english() -> Language {
         sprint: String::from("Sprint"),
         comment: String::from("Comment"),
         click_to_sort: String::from("Click to sort"),
+        shift_click_to_sort_too: String::from("Shift-click to sort by this column too"),
         right_click_to_filter: String::from(
             "Right click to filter out\nShift Right click to only show this",
         ),
         no_specific_language: String::from("No Specific Language"),
+        set_deadline: String::from("Set a deadline"),
+        clear_deadline: String::from("Remove the deadline"),
 
         settings: String::from("Settings"),
         file: String::from("File"),
         edit: String::from("Edit"),
         clear_done: String::from("Clear Done"),
         clear_present_soon: String::from("Clear Present Soon"),
+        undo: String::from("Undo"),
+        redo: String::from("Redo"),
+        confirm_clear_done: String::from("Mark every achievement in this course as not done? Ctrl+Z takes it back."),
+        confirm_clear_present_soon: String::from("Stop presenting every achievement in this course soon? Ctrl+Z takes it back."),
         clear_filters: String::from("Clear Filters"),
+        search: String::from("Search"),
+        search_help: String::from("Words match the id, title or comment. Fields: done, soon, grade, type, lang, sprint, deadline, id, title, comment, e.g. grade>=4, lang:C, deadline<2024-10-01. ! leaves out what a term matches. Press Enter to search."),
+        presets: String::from("Presets"),
+        preset_name: String::from("Name"),
+        save_preset: String::from("Save"),
+        save_preset_hint: String::from("Save the filters and sorting in use for this course"),
+        no_shortcut: String::from("No shortcut"),
         save: String::from("Save"),
 
         date: String::from("Date"),
@@ -101,10 +197,17 @@ pub fn get_english() -> Language {
         target: String::from("Target"),
         minimum_to_reach_target_grade: String::from("Minimum to reach target grade"),
         click_to_hide_passed_labs: String::from("Click to hide passed labs"),
+        plan: String::from("Plan"),
+        click_to_pin: String::from("Click to pin to this day"),
+        click_to_unpin: String::from("Click to unpin"),
+        pin_achievement: String::from("Pin achievement"),
         click_to_show_passed_labs: String::from("Click to show passed labs"),
         click_to_show_achievements_done: String::from("Click to show achievements done"),
         click_to_show_achievements_left: String::from("Click to show achievements left"),
         lab: String::from("Lab"),
+        studium: String::from("Studium"),
+        special: String::from("Special"),
+        report: String::from("Report"),
         grade3: String::from("Grade 3"),
         grade4: String::from("Grade 4"),
         grade5: String::from("Grade 5"),
@@ -115,6 +218,19 @@ pub fn get_english() -> Language {
         project: String::from("Project"),
         other: String::from("Other"),
         total: String::from("Total"),
+        grade_reached: String::from("Grade reached"),
+        no_grade_yet: String::from("None yet"),
+        missing_for_grade: String::from("Missing for grade"),
+        cheapest_path: String::from("Cheapest path"),
+        what_if: String::from("What If"),
+        what_if_hint: String::from("Try changes here without saving anything. Numbers show real → simulated."),
+        reset: String::from("Reset"),
+        labs: String::from("Labs"),
+        edit_labs: String::from("Edit Labs"),
+        add_lab: String::from("Add Lab"),
+        cancelled: String::from("Cancelled"),
+        label: String::from("Label"),
+        lab_date_hint: String::from("Dates are written like Sep 2, 2024"),
 
         font_size: String::from("Font Size"),
         dark_mode: String::from("Dark Mode"),
@@ -124,6 +240,58 @@ pub fn get_english() -> Language {
         git: String::from("Use Git To Sync"),
         burndown: String::from("Burndown csv"),
         generate: String::from("Generate"),
+        burndown_chart: String::from("Burndown"),
+        remaining: String::from("Remaining"),
+        remaining_after_planned: String::from("Remaining After Planned"),
+        ideal: String::from("Ideal"),
+        refresh: String::from("Refresh"),
+        no_history: String::from("Could not read the git history"),
+        course: String::from("Course"),
+        courses: String::from("Courses"),
+        data_dir: String::from("Data Directory"),
+        data_dir_hint: String::from("Start with --data-dir <path> to use another directory"),
+        add_course: String::from("Add Course Directory"),
+        remove: String::from("Remove"),
+
+        broken_rows: String::from("Broken Achievements"),
+        broken_rows_description: String::from("These rows could not be read from"),
+        unreadable_file: String::from("Could not read"),
+        unreadable_file_description: String::from(
+            "Nothing is saved to the file until it is read again or reset to defaults.",
+        ),
+        read_again: String::from("Read Again"),
+        row: String::from("Row"),
+        invalid_value: String::from("Invalid value"),
+        fix: String::from("Fix"),
+        skip: String::from("Skip"),
+        use_default: String::from("Use Default"),
+        skip_all: String::from("Skip All"),
+        reset_to_defaults: String::from("Reset All To Defaults"),
+
+        sync: String::from("Sync"),
+        syncing: String::from("Syncing..."),
+        syncing_before_closing: String::from("Syncing with git before closing..."),
+        last_sync: String::from("Synced"),
+        sync_failed: String::from("Sync failed"),
+        changes_kept_locally: String::from("Your changes are saved and committed locally."),
+        retry: String::from("Retry"),
+        quit_anyway: String::from("Quit Anyway"),
+        cancel: String::from("Cancel"),
+        commit_message: String::from("Commit message"),
+        commit_and_sync: String::from("Commit And Sync"),
+        edit_commit_message: String::from("Edit Commit Message On Exit"),
+
+        merge_conflicts: String::from("Merge Conflicts"),
+        merge_conflicts_description: String::from(
+            "These achievements were changed differently here and in the repository. Choose which version to keep",
+        ),
+        field: String::from("Field"),
+        mine: String::from("Mine"),
+        theirs: String::from("Theirs"),
+        keep_all_mine: String::from("Keep All Mine"),
+        take_all_theirs: String::from("Take All Theirs"),
+        apply_merge: String::from("Apply Merge"),
+        cancel_merge: String::from("Cancel Merge"),
     }
 }
 
@@ -140,17 +308,31 @@ pub fn get_swedish() -> Language {
         sprint: String::from("Sprint"),
         comment: String::from("Kommentar"),
         click_to_sort: String::from("Klicka för att sortera"),
+        shift_click_to_sort_too: String::from("Shift-klicka för att också sortera efter den här kolumnen"),
         right_click_to_filter: String::from(
             "Högerklicka för att filtrera bort\nSkift + Högerklicka för att visa endast denna",
         ),
         no_specific_language: String::from("Inget Specifikt Språk"),
+        set_deadline: String::from("Sätt en deadline"),
+        clear_deadline: String::from("Ta bort deadline"),
 
         settings: String::from("Inställningar"),
         file: String::from("Arkiv"),
         edit: String::from("Redigera"),
         clear_done: String::from("Rensa Färdiga"),
         clear_present_soon: String::from("Rensa Presentera Snart"),
+        undo: String::from("Ångra"),
+        redo: String::from("Gör om"),
+        confirm_clear_done: String::from("Markera alla achievements i kursen som inte färdiga? Ctrl+Z ångrar det."),
+        confirm_clear_present_soon: String::from("Sluta presentera alla achievements i kursen snart? Ctrl+Z ångrar det."),
         clear_filters: String::from("Rensa Filter"),
+        search: String::from("Sök"),
+        search_help: String::from("Ord matchar id, titel eller kommentar. Fält: done, soon, grade, type, lang, sprint, deadline, id, title, comment, t.ex. grade>=4, lang:C, deadline<2024-10-01. ! tar bort det en term matchar. Tryck Enter för att söka."),
+        presets: String::from("Förval"),
+        preset_name: String::from("Namn"),
+        save_preset: String::from("Spara"),
+        save_preset_hint: String::from("Spara filtren och sorteringen som används för den här kursen"),
+        no_shortcut: String::from("Inget kortkommando"),
         save: String::from("Spara"),
 
         date: String::from("Datum"),
@@ -159,12 +341,19 @@ pub fn get_swedish() -> Language {
         target: String::from("Mål"),
         minimum_to_reach_target_grade: String::from("Minimum för att nå betyg"),
         click_to_hide_passed_labs: String::from("Klicka för att dölja passerade labbar"),
+        plan: String::from("Plan"),
+        click_to_pin: String::from("Klicka för att fästa vid den här dagen"),
+        click_to_unpin: String::from("Klicka för att lossa"),
+        pin_achievement: String::from("Fäst prestation"),
         click_to_show_passed_labs: String::from("Klicka för att visa passerade labbar"),
         click_to_show_achievements_done: String::from("Klicka för att visa färdiga achievements"),
         click_to_show_achievements_left: String::from(
             "Klicka för att visa kvarvarande achievements",
         ),
-        lab: String::from("Lab"),
+        lab: String::from("Labb"),
+        studium: String::from("Studium"),
+        special: String::from("Special"),
+        report: String::from("Rapport"),
         grade3: String::from("Betyg 3"),
         grade4: String::from("Betyg 4"),
         grade5: String::from("Betyg 5"),
@@ -175,6 +364,19 @@ pub fn get_swedish() -> Language {
         project: String::from("Projekt"),
         other: String::from("Övrigt"),
         total: String::from("Totalt"),
+        grade_reached: String::from("Uppnått betyg"),
+        no_grade_yet: String::from("Inget än"),
+        missing_for_grade: String::from("Saknas för betyg"),
+        cheapest_path: String::from("Kortaste vägen"),
+        what_if: String::from("Tänk Om"),
+        what_if_hint: String::from("Prova ändringar här utan att spara något. Siffrorna visar verkligt → simulerat."),
+        reset: String::from("Återställ"),
+        labs: String::from("Labbar"),
+        edit_labs: String::from("Redigera Labbar"),
+        add_lab: String::from("Lägg Till Labb"),
+        cancelled: String::from("Inställd"),
+        label: String::from("Etikett"),
+        lab_date_hint: String::from("Datum skrivs som Sep 2, 2024"),
 
         font_size: String::from("Textstorlek"),
         dark_mode: String::from("Mörkt Tema"),
@@ -184,5 +386,57 @@ pub fn get_swedish() -> Language {
         git: String::from("Använd Git För Att Synka"),
         burndown: String::from("Burndown csv"),
         generate: String::from("Generera"),
+        burndown_chart: String::from("Burndown"),
+        remaining: String::from("Kvar"),
+        remaining_after_planned: String::from("Kvar Efter Planerade"),
+        ideal: String::from("Ideal"),
+        refresh: String::from("Uppdatera"),
+        no_history: String::from("Kunde inte läsa git-historiken"),
+        course: String::from("Kurs"),
+        courses: String::from("Kurser"),
+        data_dir: String::from("Datakatalog"),
+        data_dir_hint: String::from("Starta med --data-dir <sökväg> för att använda en annan katalog"),
+        add_course: String::from("Lägg Till Kurskatalog"),
+        remove: String::from("Ta Bort"),
+
+        broken_rows: String::from("Trasiga Achievements"),
+        broken_rows_description: String::from("Dessa rader kunde inte läsas från"),
+        unreadable_file: String::from("Kunde inte läsa"),
+        unreadable_file_description: String::from(
+            "Inget sparas i filen förrän den läses in igen eller återställs till standard.",
+        ),
+        read_again: String::from("Läs In Igen"),
+        row: String::from("Rad"),
+        invalid_value: String::from("Ogiltigt värde"),
+        fix: String::from("Rätta"),
+        skip: String::from("Hoppa Över"),
+        use_default: String::from("Använd Standard"),
+        skip_all: String::from("Hoppa Över Alla"),
+        reset_to_defaults: String::from("Återställ Alla Till Standard"),
+
+        sync: String::from("Synka"),
+        syncing: String::from("Synkar..."),
+        syncing_before_closing: String::from("Synkar med git innan stängning..."),
+        last_sync: String::from("Synkad"),
+        sync_failed: String::from("Synkning misslyckades"),
+        changes_kept_locally: String::from("Dina ändringar är sparade och lokalt committade."),
+        retry: String::from("Försök Igen"),
+        quit_anyway: String::from("Avsluta Ändå"),
+        cancel: String::from("Avbryt"),
+        commit_message: String::from("Commit-meddelande"),
+        commit_and_sync: String::from("Committa Och Synka"),
+        edit_commit_message: String::from("Redigera Commit-meddelande Vid Avslut"),
+
+        merge_conflicts: String::from("Sammanslagningskonflikter"),
+        merge_conflicts_description: String::from(
+            "Dessa achievements ändrades olika här och i repot. Välj vilken version som ska behållas",
+        ),
+        field: String::from("Fält"),
+        mine: String::from("Mina"),
+        theirs: String::from("Deras"),
+        keep_all_mine: String::from("Behåll Alla Mina"),
+        take_all_theirs: String::from("Ta Alla Deras"),
+        apply_merge: String::from("Slå Ihop"),
+        cancel_merge: String::from("Avbryt Sammanslagning"),
     }
 }
@@ -0,0 +1,42 @@
+//! Course achievements: what a course asks for, what you have done and how far you have left.
+//!
+//! The window and the command line in this package are built on this library,
+//! and other tools can use it the same way:
+//!
+//! ```no_run
+//! use achievements_enhanced::store::AchievementStore;
+//! use std::path::Path;
+//!
+//! let mut store = AchievementStore::open(Path::new("courses/ioopm"));
+//! store.set_done("A3", true);
+//! for achievement in store.query(|achievement| achievement.grade == 5 && !achievement.done) {
+//!     println!("{} {}", achievement.id, achievement.title);
+//! }
+//! println!("{} left after the first session", store.progress_tracker.days[0].target);
+//! store.save_achievements().unwrap();
+//! ```
+//!
+//! - [`course`] reads course definitions: achievements, labs, sprints and grade rules.
+//! - [`grades`] checks the achievements done against the grade rules.
+//! - [`store`] loads, changes, queries and saves the achievements of one course.
+//! - [`query`] filters achievements by queries such as `grade>=4 lang:C !done`.
+//! - [`sort`] orders achievements by several fields, with ids in natural order.
+//! - [`history`] keeps the changes made through a store so they can be undone.
+//! - [`progress_tracker`] computes how many achievements should be left after each session.
+//! - [`simulation`] tries out changes on a copy of a course without saving them.
+//! - [`burndown`] and [`git`] read the history of a course from its git repository.
+
+pub mod achievement_csv;
+pub mod achievements;
+pub mod burndown;
+pub mod commit_message;
+pub mod course;
+pub mod git;
+pub mod grades;
+pub mod history;
+pub mod merge;
+pub mod progress_tracker;
+pub mod query;
+pub mod simulation;
+pub mod sort;
+pub mod store;
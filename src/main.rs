@@ -1,19 +1,32 @@
-mod achievement_csv;
 mod achievement_ui;
-mod achievements;
 mod application;
-mod burndown;
-mod default_values;
-mod git;
+mod burndown_ui;
+mod calendar_ui;
+mod cli;
+mod data_dir;
+mod git_worker;
 mod langs;
 mod main_ui;
-mod progress_tracker;
+mod merge_ui;
+mod presets;
+mod presets_ui;
 mod progress_tracker_ui;
+mod recovery_ui;
+mod search_ui;
 mod settings_ui;
+mod sync_ui;
 
+use clap::Parser;
 use eframe::NativeOptions;
 
 fn main() -> Result<(), eframe::Error> {
+    let cli = cli::Cli::parse();
+    let data_dir = data_dir::data_dir(cli.data_dir);
+
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(data_dir, cli.course, command));
+    }
+
     let mut native_options = NativeOptions::default();
 
     native_options.viewport.maximized = Some(true);
@@ -21,6 +34,6 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "Achievements Enhanced",
         native_options,
-        Box::new(|cc| Ok(Box::new(application::Application::new(cc)))),
+        Box::new(|cc| Ok(Box::new(application::Application::new(cc, data_dir)))),
     )
 }
@@ -1,4 +1,4 @@
-use crate::application::{Application, Filters};
+use crate::application::{Application, BulkEdit, Filters};
 use eframe::egui;
 
 impl Application {
@@ -32,6 +32,20 @@ impl Application {
                             memory.close_popup();
                         });
                     }
+                    if self.settings.git {
+                        let sync_button = ui
+                            .label(self.language.sync.clone())
+                            .on_hover_cursor(egui::CursorIcon::PointingHand);
+                        if sync_button.hovered() {
+                            sync_button.clone().highlight();
+                        }
+                        if sync_button.clicked() {
+                            self.start_git(crate::git_worker::GitJob::Sync);
+                            ui.memory_mut(|memory| {
+                                memory.close_popup();
+                            });
+                        }
+                    }
                 },
             );
             let edit_button = ui.button(egui::RichText::new(self.language.edit.clone()).font(
@@ -47,6 +61,30 @@ impl Application {
                 &edit_button,
                 egui::containers::popup::PopupCloseBehavior::CloseOnClickOutside,
                 |ui| {
+                    let undo_button = ui
+                        .label(format!("{} (Ctrl+Z)", self.language.undo))
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    if undo_button.hovered() {
+                        undo_button.clone().highlight();
+                    }
+                    if undo_button.clicked() {
+                        self.undo();
+                        ui.memory_mut(|memory| {
+                            memory.close_popup();
+                        });
+                    }
+                    let redo_button = ui
+                        .label(format!("{} (Ctrl+Shift+Z)", self.language.redo))
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    if redo_button.hovered() {
+                        redo_button.clone().highlight();
+                    }
+                    if redo_button.clicked() {
+                        self.redo();
+                        ui.memory_mut(|memory| {
+                            memory.close_popup();
+                        });
+                    }
                     let clear_done_button = ui
                         .label(self.language.clear_done.clone())
                         .on_hover_cursor(egui::CursorIcon::PointingHand);
@@ -54,7 +92,7 @@ impl Application {
                         clear_done_button.clone().highlight();
                     }
                     if clear_done_button.clicked() {
-                        self.clear_done();
+                        self.confirming = Some(BulkEdit::ClearDone);
                         ui.memory_mut(|memory| {
                             memory.close_popup();
                         });
@@ -66,7 +104,7 @@ impl Application {
                         clear_present_soon_button.clone().highlight();
                     }
                     if clear_present_soon_button.clicked() {
-                        self.clear_present_soon();
+                        self.confirming = Some(BulkEdit::ClearPresentSoon);
                         ui.memory_mut(|memory| {
                             memory.close_popup();
                         });
@@ -86,6 +124,13 @@ impl Application {
                 },
             );
 
+            let presets_button = ui.button(
+                egui::RichText::new(self.language.presets.clone()).font(egui::FontId::new(
+                    self.settings.font_size * 1.5,
+                    egui::FontFamily::Proportional,
+                )),
+            );
+
             let settings_button = ui.button(
                 egui::RichText::new(self.language.settings.clone()).font(egui::FontId::new(
                     self.settings.font_size * 1.5,
@@ -106,6 +151,38 @@ impl Application {
                 ),
             ));
 
+            let burndown_button = ui.button(
+                egui::RichText::new(self.language.burndown_chart.clone()).font(egui::FontId::new(
+                    self.settings.font_size * 1.5,
+                    egui::FontFamily::Proportional,
+                )),
+            );
+
+            let mut selected_workspace = self.active_workspace;
+            egui::ComboBox::from_id_salt("Course switcher")
+                .selected_text(
+                    egui::RichText::new(self.workspace().course.name.clone()).font(
+                        egui::FontId::new(
+                            self.settings.font_size * 1.5,
+                            egui::FontFamily::Proportional,
+                        ),
+                    ),
+                )
+                .show_ui(ui, |ui| {
+                    for (i, workspace) in self.workspaces.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut selected_workspace,
+                            i,
+                            format!("{} ({})", workspace.course.name, workspace.path.display()),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text(self.language.course.clone());
+            self.switch_workspace(selected_workspace);
+
+            self.sync_status_ui(ui);
+
             if file_button.clicked() {
                 ui.memory_mut(|memory| {
                     memory.open_popup(file_popup_id);
@@ -114,14 +191,55 @@ impl Application {
                 ui.memory_mut(|memory| {
                     memory.open_popup(edit_popup_id);
                 });
+            } else if presets_button.clicked() {
+                self.editing_presets = !self.editing_presets;
             } else if settings_button.clicked() {
                 self.active_window = crate::application::ActiveWindow::Settings;
             } else if achievements_button.clicked() {
                 self.active_window = crate::application::ActiveWindow::Achievements;
             } else if progress_tracker_button.clicked() {
                 self.active_window = crate::application::ActiveWindow::ProgressTracker;
+            } else if burndown_button.clicked() {
+                self.active_window = crate::application::ActiveWindow::Burndown;
             }
         });
         ui.allocate_space(egui::vec2(0.0, 15.0));
     }
+
+    /// Asks before a change to every achievement, such as clearing all done marks, is made.
+    pub fn confirm_ui(&mut self, ctx: &egui::Context) {
+        let Some(bulk_edit) = self.confirming else {
+            return;
+        };
+        let (title, question) = match bulk_edit {
+            BulkEdit::ClearDone => (
+                self.language.clear_done.clone(),
+                self.language.confirm_clear_done.clone(),
+            ),
+            BulkEdit::ClearPresentSoon => (
+                self.language.clear_present_soon.clone(),
+                self.language.confirm_clear_present_soon.clone(),
+            ),
+        };
+
+        egui::Window::new(title.clone())
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(question);
+                ui.horizontal(|ui| {
+                    if ui.button(title).clicked() {
+                        match bulk_edit {
+                            BulkEdit::ClearDone => self.clear_done(),
+                            BulkEdit::ClearPresentSoon => self.clear_present_soon(),
+                        }
+                        self.confirming = None;
+                    }
+                    if ui.button(self.language.cancel.clone()).clicked() {
+                        self.confirming = None;
+                    }
+                });
+            });
+    }
 }
@@ -0,0 +1,447 @@
+use crate::achievement_csv;
+use crate::achievements::Achievement;
+use crate::git::{GitError, Repository};
+
+use std::path::{Path, PathBuf};
+
+/// The fields of an achievement that can genuinely conflict and have to be chosen by the user.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Field {
+    Title,
+    Deadline,
+    Grade,
+    PresentingType,
+    ProgrammingLanguage,
+    Sprint,
+}
+
+impl Field {
+    pub fn value(&self, achievement: &Achievement) -> String {
+        match self {
+            Field::Title => achievement.title.clone(),
+            Field::Deadline => achievement
+                .deadline
+                .map(|deadline| deadline.format("%a %b %d, %Y").to_string())
+                .unwrap_or_default(),
+            Field::Grade => achievement.grade.to_string(),
+            Field::PresentingType => achievement.presenting_type.to_string(),
+            Field::ProgrammingLanguage => achievement.programming_language.to_string(),
+            Field::Sprint => format!("{:?}", achievement.sprint),
+        }
+    }
+
+    fn copy(&self, from: &Achievement, to: &mut Achievement) {
+        match self {
+            Field::Title => to.title = from.title.clone(),
+            Field::Deadline => to.deadline = from.deadline,
+            Field::Grade => to.grade = from.grade,
+            Field::PresentingType => to.presenting_type = from.presenting_type.clone(),
+            Field::ProgrammingLanguage => {
+                to.programming_language = from.programming_language.clone()
+            }
+            Field::Sprint => to.sprint = from.sprint.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+/// An achievement where both sides changed the same fields differently.
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    pub ours: Achievement,
+    pub theirs: Achievement,
+    /// The conflicting fields and which side the user currently picks for each.
+    pub fields: Vec<(Field, Side)>,
+}
+
+/// A conflicted achievements.csv, merged row by row on `id`.
+#[derive(Clone, Debug)]
+pub struct PendingMerge {
+    /// The conflicted file, as an absolute path.
+    pub file: PathBuf,
+    /// The merged achievements, using our side for every conflicting field.
+    pub merged: Vec<Achievement>,
+    pub conflicts: Vec<Conflict>,
+    /// Our version of the file as committed before the pull.
+    pub ours: String,
+}
+
+impl PendingMerge {
+    /// Reads the three versions git keeps of a conflicted file and merges them.
+    pub fn from_git(repo: &impl Repository, file: &Path) -> Result<Self, GitError> {
+        let base = repo.show_stage(1, file).ok();
+        let ours = repo.show_stage(2, file)?;
+        let theirs = repo.show_stage(3, file)?;
+        Self::from_stages(file, base.as_deref(), &ours, &theirs)
+    }
+
+    /// Merges the contents of the common ancestor, our and their version of `file`.
+    ///
+    /// Fails with [`GitError::Unreadable`] if any version has rows that cannot be read,
+    /// since merging without them would drop them from the result.
+    pub fn from_stages(
+        file: &Path,
+        base: Option<&str>,
+        ours: &str,
+        theirs: &str,
+    ) -> Result<Self, GitError> {
+        let base = base.map(|base| parse(file, base)).transpose()?;
+        let theirs = parse(file, theirs)?;
+        let (merged, conflicts) = merge(base.as_deref(), &parse(file, ours)?, &theirs);
+        Ok(Self {
+            file: file.to_path_buf(),
+            merged,
+            conflicts,
+            ours: ours.to_string(),
+        })
+    }
+
+    /// The merged achievements with the user's choices applied.
+    pub fn resolve(&self) -> Vec<Achievement> {
+        let mut resolved = self.merged.clone();
+        for conflict in self.conflicts.iter() {
+            let Some(achievement) = resolved
+                .iter_mut()
+                .find(|achievement| achievement.id == conflict.ours.id)
+            else {
+                continue;
+            };
+            for (field, side) in conflict.fields.iter() {
+                if *side == Side::Theirs {
+                    field.copy(&conflict.theirs, achievement);
+                }
+            }
+        }
+        resolved
+    }
+}
+
+fn parse(file: &Path, contents: &str) -> Result<Vec<Achievement>, GitError> {
+    match achievement_csv::read_achievements_from_str(contents) {
+        Ok(read) if read.broken_rows.is_empty() => Ok(read.achievements),
+        _ => Err(GitError::Unreadable(file.to_path_buf())),
+    }
+}
+
+/// Three-way merge keyed on achievement `id`.
+///
+/// A field changed on only one side takes that change. When both sides changed a field,
+/// `done` and `present_soon` are true if either side says so and comments are concatenated;
+/// any other field is reported as a conflict and keeps our value until the user decides.
+pub fn merge(
+    base: Option<&[Achievement]>,
+    ours: &[Achievement],
+    theirs: &[Achievement],
+) -> (Vec<Achievement>, Vec<Conflict>) {
+    let find = |achievements: &[Achievement], id: &str| {
+        achievements
+            .iter()
+            .find(|achievement| achievement.id == id)
+            .cloned()
+    };
+
+    let mut merged = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for our in ours.iter() {
+        let base = base.and_then(|base| find(base, &our.id));
+        match find(theirs, &our.id) {
+            Some(their) => {
+                let (achievement, fields) = merge_achievement(base.as_ref(), our, &their);
+                if !fields.is_empty() {
+                    conflicts.push(Conflict {
+                        ours: our.clone(),
+                        theirs: their,
+                        fields: fields
+                            .into_iter()
+                            .map(|field| (field, Side::Ours))
+                            .collect(),
+                    });
+                }
+                merged.push(achievement);
+            }
+            // They removed it. Keep it only if we changed it since.
+            None => {
+                if base.as_ref() != Some(our) {
+                    merged.push(our.clone());
+                }
+            }
+        }
+    }
+
+    for their in theirs.iter() {
+        if find(ours, &their.id).is_some() {
+            continue;
+        }
+        // We removed it. Keep it only if they changed it since.
+        let base = base.and_then(|base| find(base, &their.id));
+        if base.as_ref() != Some(their) {
+            merged.push(their.clone());
+        }
+    }
+
+    (merged, conflicts)
+}
+
+fn three_way<T: PartialEq + Clone>(base: Option<&T>, ours: &T, theirs: &T) -> Option<T> {
+    if ours == theirs {
+        return Some(ours.clone());
+    }
+    match base {
+        Some(base) if base == ours => Some(theirs.clone()),
+        Some(base) if base == theirs => Some(ours.clone()),
+        _ => None,
+    }
+}
+
+fn merge_achievement(
+    base: Option<&Achievement>,
+    ours: &Achievement,
+    theirs: &Achievement,
+) -> (Achievement, Vec<Field>) {
+    let mut merged = ours.clone();
+    let mut conflicts = Vec::new();
+
+    merged.done = three_way(base.map(|base| &base.done), &ours.done, &theirs.done)
+        .unwrap_or(ours.done || theirs.done);
+    merged.present_soon = three_way(
+        base.map(|base| &base.present_soon),
+        &ours.present_soon,
+        &theirs.present_soon,
+    )
+    .unwrap_or(ours.present_soon || theirs.present_soon);
+    merged.comment = three_way(
+        base.map(|base| &base.comment),
+        &ours.comment,
+        &theirs.comment,
+    )
+    .unwrap_or_else(|| match (&ours.comment, &theirs.comment) {
+        (Some(our), Some(their)) => Some(format!("{}; {}", our, their)),
+        (Some(comment), None) | (None, Some(comment)) => Some(comment.clone()),
+        (None, None) => None,
+    });
+
+    for field in [
+        Field::Title,
+        Field::Deadline,
+        Field::Grade,
+        Field::PresentingType,
+        Field::ProgrammingLanguage,
+        Field::Sprint,
+    ] {
+        let base = base.map(|base| field.value(base));
+        match three_way(base.as_ref(), &field.value(ours), &field.value(theirs)) {
+            Some(value) if value == field.value(ours) => {}
+            Some(_) => field.copy(theirs, &mut merged),
+            None => conflicts.push(field),
+        }
+    }
+
+    (merged, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::course::Course;
+
+    fn base() -> Vec<Achievement> {
+        let mut achievements = Course::bundled().achievements[..3].to_vec();
+        for achievement in achievements.iter_mut() {
+            achievement.grade = 3;
+            achievement.comment = None;
+        }
+        achievements
+    }
+
+    /// achievements.csv with one row per line after the header.
+    fn csv(rows: &[&str]) -> String {
+        let mut contents = achievement_csv::COLUMNS.join(",");
+        for row in rows {
+            contents.push('\n');
+            contents.push_str(row);
+        }
+        contents
+    }
+
+    fn ids(achievements: &[Achievement]) -> Vec<&str> {
+        achievements
+            .iter()
+            .map(|achievement| achievement.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn three_way_takes_the_side_that_changed() {
+        assert_eq!(three_way(Some(&1), &1, &2), Some(2));
+        assert_eq!(three_way(Some(&1), &2, &1), Some(2));
+        assert_eq!(three_way(Some(&1), &2, &2), Some(2));
+        assert_eq!(three_way(None, &2, &2), Some(2));
+        assert_eq!(three_way(Some(&1), &2, &3), None);
+        assert_eq!(three_way(None, &2, &3), None);
+    }
+
+    #[test]
+    fn edits_on_one_side_are_taken_from_either_side() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours[0].done = true;
+        ours[2].comment = Some(String::from("ours"));
+        theirs[0].title = String::from("Theirs");
+        theirs[1].grade = 5;
+
+        let (merged, conflicts) = merge(Some(&base), &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert!(merged[0].done);
+        assert_eq!(merged[0].title, "Theirs");
+        assert_eq!(merged[1].grade, 5);
+        assert_eq!(merged[2].comment.as_deref(), Some("ours"));
+    }
+
+    #[test]
+    fn the_same_edit_on_both_sides_is_not_a_conflict() {
+        let base = base();
+        let mut ours = base.clone();
+        ours[0].grade = 5;
+        ours[0].title = String::from("Both");
+        ours[0].done = true;
+        let theirs = ours.clone();
+
+        let (merged, conflicts) = merge(Some(&base), &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(merged, ours);
+    }
+
+    #[test]
+    fn different_edits_of_a_field_conflict_and_keep_ours_until_resolved() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours[0].title = String::from("Ours");
+        theirs[0].title = String::from("Theirs");
+        ours[0].grade = 4;
+        theirs[0].grade = 5;
+        theirs[0].done = true;
+
+        let (merged, conflicts) = merge(Some(&base), &ours, &theirs);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].ours.id, base[0].id);
+        assert_eq!(
+            conflicts[0].fields,
+            vec![(Field::Title, Side::Ours), (Field::Grade, Side::Ours)]
+        );
+        assert_eq!(merged[0].title, "Ours");
+        assert_eq!(merged[0].grade, 4);
+        assert!(merged[0].done);
+
+        let mut pending = PendingMerge {
+            file: PathBuf::from(achievement_csv::ACHIEVEMENTS_FILE),
+            merged,
+            conflicts,
+            ours: String::new(),
+        };
+        pending.conflicts[0].fields[0].1 = Side::Theirs;
+        let resolved = pending.resolve();
+        assert_eq!(resolved[0].title, "Theirs");
+        assert_eq!(resolved[0].grade, 4);
+    }
+
+    #[test]
+    fn marks_are_kept_if_either_side_set_them_without_a_base() {
+        let mut ours = base();
+        let mut theirs = base();
+        ours[0].done = true;
+        theirs[0].present_soon = true;
+
+        let (merged, conflicts) = merge(None, &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert!(merged[0].done);
+        assert!(merged[0].present_soon);
+    }
+
+    #[test]
+    fn achievements_added_on_one_side_are_kept() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        let mut added = Course::bundled().achievements[3].clone();
+        theirs.push(added.clone());
+        added.id = String::from("X1");
+        ours.push(added);
+
+        let (merged, conflicts) = merge(Some(&base), &ours, &theirs);
+        assert!(conflicts.is_empty());
+        let mut expected = ids(&base);
+        expected.push("X1");
+        expected.push(&theirs[3].id);
+        assert_eq!(ids(&merged), expected);
+    }
+
+    #[test]
+    fn achievements_removed_on_one_side_are_dropped_unless_the_other_side_changed_them() {
+        let base = base();
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours.remove(0);
+        theirs.remove(1);
+        theirs[0].done = true;
+
+        let (merged, conflicts) = merge(Some(&base), &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(ids(&merged), vec![base[2].id.as_str(), base[0].id.as_str()]);
+        assert!(merged[1].done);
+    }
+
+    #[test]
+    fn comments_changed_on_both_sides_are_concatenated() {
+        let mut base = base();
+        base[0].comment = Some(String::from("base"));
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+        ours[0].comment = Some(String::from("ours"));
+        theirs[0].comment = Some(String::from("theirs"));
+        ours[1].comment = Some(String::from("only ours"));
+        theirs[1].comment = Some(String::from("only theirs"));
+        ours[2].comment = None;
+        theirs[2].comment = Some(String::from("theirs"));
+
+        let (merged, conflicts) = merge(Some(&base), &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(merged[0].comment.as_deref(), Some("ours; theirs"));
+        assert_eq!(merged[1].comment.as_deref(), Some("only ours; only theirs"));
+        assert_eq!(merged[2].comment.as_deref(), Some("theirs"));
+    }
+
+    #[test]
+    fn stages_with_unreadable_rows_are_not_merged() {
+        let row = |grade: &str| {
+            format!(
+                "A1,https://example.com/A1,Title,\"Sep 5, 2024\",false,false,{},Lab,C,Sprint1,",
+                grade
+            )
+        };
+        let file = Path::new("achievements.csv");
+        let good = csv(&[&row("3")]);
+        let bad = csv(&[&row("five")]);
+
+        let pending = PendingMerge::from_stages(file, Some(&good), &good, &good).unwrap();
+        assert_eq!(ids(&pending.merged), ["A1"]);
+
+        for (base, ours, theirs) in [
+            (&bad, &good, &good),
+            (&good, &bad, &good),
+            (&good, &good, &bad),
+        ] {
+            assert!(matches!(
+                PendingMerge::from_stages(file, Some(base), ours, theirs),
+                Err(GitError::Unreadable(_))
+            ));
+        }
+    }
+}
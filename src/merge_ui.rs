@@ -0,0 +1,111 @@
+use crate::application::Application;
+use crate::langs::Language;
+use achievements_enhanced::merge::{Field, Side};
+use eframe::egui;
+
+fn field_name(language: &Language, field: Field) -> String {
+    match field {
+        Field::Title => language.title.clone(),
+        Field::Deadline => language.deadline.clone(),
+        Field::Grade => language.grade.clone(),
+        Field::PresentingType => language.presenting_type.clone(),
+        Field::ProgrammingLanguage => language.programming_language.clone(),
+        Field::Sprint => language.sprint.clone(),
+    }
+}
+
+impl Application {
+    /// Shows both versions of every conflicting achievement side by side
+    /// so the user can pick one per field before the merge is committed.
+    pub fn merge_ui(&mut self, ctx: &egui::Context) {
+        if self.pending_merges.is_empty() {
+            return;
+        }
+
+        let language = &self.language;
+        let mut apply = false;
+        let mut abort = false;
+
+        egui::Window::new(language.merge_conflicts.clone())
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(language.merge_conflicts_description.clone());
+                ui.allocate_space(egui::vec2(0.0, 10.0));
+
+                egui::ScrollArea::vertical()
+                    .max_height(self.settings.font_size * 30.0)
+                    .show(ui, |ui| {
+                        for (i, merge) in self.pending_merges.iter_mut().enumerate() {
+                            if merge.conflicts.is_empty() {
+                                continue;
+                            }
+                            ui.label(merge.file.display().to_string());
+                            egui::Grid::new(("Merge Grid", i))
+                                .striped(true)
+                                .spacing(egui::vec2(
+                                    self.settings.font_size,
+                                    self.settings.font_size / 2.0,
+                                ))
+                                .show(ui, |ui| {
+                                    ui.label(language.id.clone());
+                                    ui.label(language.field.clone());
+                                    ui.label(language.mine.clone());
+                                    ui.label(language.theirs.clone());
+                                    ui.end_row();
+
+                                    for conflict in merge.conflicts.iter_mut() {
+                                        for (field, side) in conflict.fields.iter_mut() {
+                                            ui.label(conflict.ours.id.clone());
+                                            ui.label(field_name(language, *field));
+                                            ui.radio_value(
+                                                side,
+                                                Side::Ours,
+                                                field.value(&conflict.ours),
+                                            );
+                                            ui.radio_value(
+                                                side,
+                                                Side::Theirs,
+                                                field.value(&conflict.theirs),
+                                            );
+                                            ui.end_row();
+                                        }
+                                    }
+                                });
+                            ui.allocate_space(egui::vec2(0.0, 10.0));
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    let mut pick_all = None;
+                    if ui.button(language.keep_all_mine.clone()).clicked() {
+                        pick_all = Some(Side::Ours);
+                    }
+                    if ui.button(language.take_all_theirs.clone()).clicked() {
+                        pick_all = Some(Side::Theirs);
+                    }
+                    if let Some(pick) = pick_all {
+                        for merge in self.pending_merges.iter_mut() {
+                            for conflict in merge.conflicts.iter_mut() {
+                                for (_, side) in conflict.fields.iter_mut() {
+                                    *side = pick;
+                                }
+                            }
+                        }
+                    }
+                    if ui.button(language.apply_merge.clone()).clicked() {
+                        apply = true;
+                    }
+                    if ui.button(language.cancel_merge.clone()).clicked() {
+                        abort = true;
+                    }
+                });
+            });
+
+        if apply {
+            self.apply_merges();
+        } else if abort {
+            self.abort_merges();
+        }
+    }
+}
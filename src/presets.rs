@@ -0,0 +1,107 @@
+use crate::application::{Direction, Fieled};
+
+use std::path::Path;
+
+pub const PRESETS_FILE: &str = "presets.toml";
+
+/// A named combination of filters and sort order, saved with the course.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Preset {
+    pub name: String,
+    /// The filters written as a search query.
+    pub query: String,
+    /// The fields to sort by, most important first.
+    #[serde(default)]
+    pub sort: Vec<(Fieled, Direction)>,
+    /// Ctrl and this digit apply the preset.
+    pub key: Option<u8>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SerializablePresets {
+    #[serde(default)]
+    presets: Vec<Preset>,
+}
+
+/// Reads `presets.toml` in the course directory `path`. A missing file has no presets.
+pub fn load(path: &Path) -> Vec<Preset> {
+    let Ok(contents) = std::fs::read_to_string(path.join(PRESETS_FILE)) else {
+        return Vec::new();
+    };
+    match toml::from_str::<SerializablePresets>(&contents) {
+        Ok(presets) => presets.presets,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", PRESETS_FILE, e);
+            Vec::new()
+        }
+    }
+}
+
+pub fn save(path: &Path, presets: &[Preset]) {
+    let presets = SerializablePresets {
+        presets: presets.to_vec(),
+    };
+    let result = toml::to_string(&presets)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            std::fs::write(path.join(PRESETS_FILE), contents).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        eprintln!("Error saving {}: {}", PRESETS_FILE, e);
+    }
+}
+
+/// Binds the digit `key` to the preset at `index`, taking it from any other preset.
+pub fn set_key(presets: &mut [Preset], index: usize, key: Option<u8>) {
+    for preset in presets.iter_mut() {
+        if key.is_some() && preset.key == key {
+            preset.key = None;
+        }
+    }
+    presets[index].key = key;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn preset(name: &str, key: Option<u8>) -> Preset {
+        Preset {
+            name: name.to_string(),
+            query: format!(r#"!done title:"{}""#, name),
+            sort: vec![
+                (Fieled::Grade, Direction::Descending),
+                (Fieled::ID, Direction::Ascending),
+            ],
+            key,
+        }
+    }
+
+    #[test]
+    fn saved_presets_load_back_the_same() {
+        let dir = TempDir::new().unwrap();
+        assert!(load(dir.path()).is_empty());
+
+        let presets = vec![preset("Left", Some(1)), preset("All", None)];
+        save(dir.path(), &presets);
+        assert_eq!(load(dir.path()), presets);
+    }
+
+    #[test]
+    fn a_key_moves_to_the_preset_it_is_given_to() {
+        let mut presets = vec![preset("First", Some(1)), preset("Second", Some(2))];
+
+        set_key(&mut presets, 1, Some(1));
+        assert_eq!(presets[0].key, None);
+        assert_eq!(presets[1].key, Some(1));
+
+        set_key(&mut presets, 0, Some(2));
+        assert_eq!(presets[0].key, Some(2));
+        assert_eq!(presets[1].key, Some(1));
+
+        set_key(&mut presets, 1, None);
+        assert_eq!(presets[0].key, Some(2));
+        assert_eq!(presets[1].key, None);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::application::Application;
+use eframe::egui;
+
+impl Application {
+    /// Window for applying, binding, removing and saving presets.
+    /// A window rather than a popup, as the shortcut pickers are popups of their own.
+    pub fn presets_ui(&mut self, ctx: &egui::Context) {
+        if !self.editing_presets {
+            return;
+        }
+
+        let mut open = true;
+        egui::Window::new(self.language.presets.clone())
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| self.presets_window(ui));
+        if !open {
+            self.editing_presets = false;
+        }
+    }
+
+    fn presets_window(&mut self, ui: &mut egui::Ui) {
+        let mut apply = None;
+        let mut remove = None;
+        let mut bind = None;
+
+        egui::Grid::new("Presets Grid").show(ui, |ui| {
+            for (i, preset) in self.presets.iter().enumerate() {
+                let preset_button = ui
+                    .label(preset.name.clone())
+                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                    .on_hover_text(preset.query.clone());
+                if preset_button.hovered() {
+                    preset_button.clone().highlight();
+                }
+                if preset_button.clicked() {
+                    apply = Some(i);
+                }
+
+                let shortcut = |key: Option<u8>| match key {
+                    Some(key) => format!("Ctrl+{}", key),
+                    None => self.language.no_shortcut.clone(),
+                };
+                egui::ComboBox::from_id_salt(("Preset Key", i))
+                    .selected_text(shortcut(preset.key))
+                    .show_ui(ui, |ui| {
+                        for key in std::iter::once(None).chain((1..=9).map(Some)) {
+                            if ui
+                                .selectable_label(preset.key == key, shortcut(key))
+                                .clicked()
+                            {
+                                bind = Some((i, key));
+                            }
+                        }
+                    });
+
+                if ui.button(self.language.remove.clone()).clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_preset)
+                    .hint_text(self.language.preset_name.clone()),
+            );
+            let name = self.new_preset.trim().to_string();
+            if ui
+                .add_enabled(
+                    !name.is_empty(),
+                    egui::Button::new(self.language.save_preset.clone()),
+                )
+                .on_hover_text(self.language.save_preset_hint.clone())
+                .clicked()
+            {
+                self.save_preset(&name);
+                self.new_preset.clear();
+            }
+        });
+
+        if let Some(i) = apply {
+            self.apply_preset(i);
+        }
+        if let Some((i, key)) = bind {
+            self.set_preset_key(i, key);
+        }
+        if let Some(i) = remove {
+            self.remove_preset(i);
+        }
+    }
+}
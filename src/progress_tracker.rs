@@ -1,69 +1,53 @@
 use crate::achievements::{Achievement, AchievementPresention, PresentationType};
+use crate::course::{self, Course, CourseError, Session};
+use crate::grades::{self, GradeStatus};
 
 use chrono::{DateTime, Local};
 
-pub const LABS: [&str; 32] = [
-    "Sep 2, 2024",
-    "Sep 4, 2024",
-    "Sep 5, 2024",
-    "Sep 9, 2024",
-    "Sep 11, 2024",
-    "Sep 12, 2024",
-    "Sep 16, 2024",
-    "Sep 19, 2024",
-    "Sep 23, 2024",
-    "Sep 26, 2024",
-    "Oct 1, 2024",
-    "Oct 3, 2024",
-    "Oct 7, 2024",
-    "Oct 10, 2024",
-    "Oct 14, 2024",
-    "Oct 17, 2024",
-    "Oct 21, 2024",
-    "Oct 25, 2024",
-    "Nov 1, 2024",
-    "Nov 4, 2024",
-    "Nov 6, 2024",
-    "Nov 11, 2024",
-    "Nov 14, 2024",
-    "Nov 19, 2024",
-    "Nov 21, 2024",
-    "Nov 25, 2024",
-    "Nov 28, 2024",
-    "Dec 2, 2024",
-    "Dec 5, 2024",
-    "Dec 9, 2024",
-    "Dec 12, 2024",
-    "Dec 16, 2024",
-];
+use std::path::Path;
 
+/// Where the pins of a course are kept, next to its `calendar.toml`.
+pub const PINS_FILE: &str = "pins.toml";
+
+/// A date with at least one occasion to present achievements,
+/// and how many achievements should be left after it.
 #[derive(Clone, Debug)]
-pub struct Lab {
+pub struct Day {
     pub date: DateTime<Local>,
+    /// The presentation types that can be presented this day.
+    pub presentations: Vec<PresentationType>,
     pub optimal: u8,
     pub minimum: u8,
     pub target: u8,
     pub current_minimum: u8,
+    /// Ids of the achievements suggested for this day. Empty for days that have passed.
+    pub plan: Vec<String>,
 }
 
-impl Lab {
-    pub fn new(date: String) -> Self {
-        let date =
-            DateTime::parse_from_str(&format!("{date} 0:0:0 +0000"), "%b %d, %Y %H:%M:%S %z")
-                .unwrap()
-                .with_timezone(&Local);
+/// An achievement the user wants to present on a specific day,
+/// kept there by the plan even when another day would be suggested.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pin {
+    pub id: String,
+    pub date: DateTime<Local>,
+}
 
-        Lab {
-            date,
-            optimal: 0,
-            minimum: 0,
-            target: 0,
-            current_minimum: 0,
-        }
-    }
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct SerializablePin {
+    pub id: String,
+    pub date: String,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct SerializablePins {
+    #[serde(default)]
+    pub pins: Vec<SerializablePin>,
 }
 
+/// Whether the tracker counts achievements left or achievements done.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum ProgressTrackerMode {
+    #[default]
     Left,
     Done,
 }
@@ -77,185 +61,680 @@ impl ProgressTrackerMode {
     }
 }
 
+/// How many achievements to present at each session.
+#[derive(Clone, Copy, PartialEq)]
+enum Pace {
+    /// As many as the session takes.
+    Fastest,
+    /// Spread evenly over the sessions left in the channel.
+    Even,
+}
+
+/// Something that changed in the store the tracker follows.
+pub enum Change<'a> {
+    /// One achievement was edited, `before` is how it was.
+    Achievement {
+        before: &'a Achievement,
+        after: &'a Achievement,
+    },
+    /// Achievements were added, removed, reordered or replaced.
+    Achievements,
+    /// The target grade, how many fit in a lab or the mode.
+    Parameters,
+    /// The dates of the labs or other sessions.
+    Calendar,
+}
+
+/// How many achievements should be left after each day, and a plan for which ones to present.
+///
+/// The tracker keeps neither the course nor the achievements; whoever owns them
+/// reports each [`Change`] so the numbers never go stale.
+#[derive(Clone, Debug)]
 pub struct ProgressTracker {
-    pub achievements: Vec<Achievement>,
     pub mode: ProgressTrackerMode,
-    pub labs: Vec<Lab>,
+    pub days: Vec<Day>,
+    pub pins: Vec<Pin>,
     pub max_per_lab: u8,
     pub target_grade: i8,
     pub optimal: u8,
     pub minimum: u8,
     pub target: u8,
     pub current_minimum: u8,
+    /// The grade reached with the achievements done and what the next one takes.
+    pub grades: GradeStatus,
 }
 
 impl ProgressTracker {
-    pub fn new(max_per_lab: u8, target_grade: i8, achievements: &Vec<Achievement>) -> Self {
-        let mut labs = Vec::new();
-        for lab in LABS.iter() {
-            labs.push(Lab::new(lab.to_string()));
-        }
-
+    pub fn new(
+        max_per_lab: u8,
+        target_grade: i8,
+        mode: ProgressTrackerMode,
+        course: &Course,
+        achievements: &[Achievement],
+    ) -> Self {
         let mut progress_tracker = ProgressTracker {
-            achievements: achievements.clone(),
-            mode: ProgressTrackerMode::Left,
-            labs,
+            mode,
+            days: Vec::new(),
+            pins: Vec::new(),
             max_per_lab,
             target_grade,
             optimal: 0,
             minimum: 0,
             target: 0,
             current_minimum: 0,
+            grades: GradeStatus::default(),
         };
 
-        progress_tracker.update();
+        progress_tracker.update(course, achievements);
         progress_tracker
     }
 
-    pub fn update(&mut self) {
-        let max_achievements = self
-            .achievements
+    /// Brings the tracker up to date after `change`, given the course and achievements after it.
+    /// Edits that cannot move any number, the plan or the grade are skipped.
+    pub fn changed(&mut self, change: Change, course: &Course, achievements: &[Achievement]) {
+        match change {
+            Change::Achievement { before, after } => {
+                let counted = |achievement: &Achievement| achievement.grade <= self.target_grade;
+                let relevant = before.id != after.id
+                    || before.done != after.done
+                    || before.present_soon != after.present_soon
+                    || before.grade != after.grade
+                    || before.presenting_type != after.presenting_type
+                    || before.sprint != after.sprint;
+                if relevant && (counted(before) || counted(after)) {
+                    self.update(course, achievements);
+                } else if relevant {
+                    // Above the target grade it only matters to the grade rules.
+                    self.grades = grades::evaluate(&course.grade_rules, achievements);
+                }
+            }
+            Change::Achievements | Change::Parameters | Change::Calendar => {
+                self.update(course, achievements)
+            }
+        }
+    }
+
+    /// Recomputes the days and totals from scratch.
+    ///
+    /// Counting is done in `usize` on achievements actually scheduled, so the counts
+    /// can never go below zero or above the number of achievements, whatever is marked done.
+    /// Courses with more than 255 achievements show 255.
+    pub fn update(&mut self, course: &Course, achievements: &[Achievement]) {
+        self.grades = grades::evaluate(&course.grade_rules, achievements);
+        let sessions = course.sessions(self.max_per_lab);
+        let achievements = achievements
             .iter()
-            .filter(|achievement| {
-                achievement.presenting_type == AchievementPresention::Single(PresentationType::Lab)
-            })
             .filter(|achievement| achievement.grade <= self.target_grade)
-            .collect::<Vec<&Achievement>>()
-            .len() as u8;
+            .collect::<Vec<&Achievement>>();
+        let now = Local::now();
 
-        self.optimal = match self.mode {
-            ProgressTrackerMode::Left => max_achievements,
-            ProgressTrackerMode::Done => 0,
+        let schedule = |pace, actual_until| {
+            Self::schedule(course, &sessions, &achievements, pace, actual_until)
         };
+        let optimal = schedule(Pace::Fastest, None);
+        let minimum = schedule(Pace::Even, None);
+        let target = schedule(Pace::Fastest, Some(now));
+        let current_minimum = schedule(Pace::Even, Some(now));
+        let plan = self.plan(course, &sessions, &achievements, now);
 
-        self.minimum = self.optimal;
-        self.target = self.optimal;
-        self.current_minimum = self.optimal;
+        let total = achievements.len();
+        let shown = |left: usize| {
+            let shown = match self.mode {
+                ProgressTrackerMode::Left => left,
+                ProgressTrackerMode::Done => total.saturating_sub(left),
+            };
+            clamp(shown)
+        };
+
+        self.days.clear();
+        for (i, session) in sessions.iter().enumerate() {
+            let last_of_day = sessions
+                .get(i + 1)
+                .is_none_or(|next| next.date != session.date);
+            match self.days.last_mut() {
+                Some(day) if day.date == session.date => {
+                    day.presentations.push(session.presentation.clone())
+                }
+                _ => self.days.push(Day {
+                    date: session.date,
+                    presentations: vec![session.presentation.clone()],
+                    optimal: 0,
+                    minimum: 0,
+                    target: 0,
+                    current_minimum: 0,
+                    plan: Vec::new(),
+                }),
+            }
+            self.days
+                .last_mut()
+                .expect("a day was just pushed")
+                .plan
+                .extend(plan[i].iter().cloned());
+            if last_of_day {
+                let day = self.days.last_mut().expect("a day was just pushed");
+                day.optimal = shown(optimal[i]);
+                day.minimum = shown(minimum[i]);
+                day.target = shown(target[i]);
+                day.current_minimum = shown(current_minimum[i]);
+            }
+        }
+
+        let last = |left: &[usize]| shown(left.last().copied().unwrap_or(total));
+        self.optimal = last(&optimal);
+        self.minimum = last(&minimum);
+        self.target = last(&target);
+        self.current_minimum = last(&current_minimum);
+    }
 
-        for (i, lab) in self.labs.clone().into_iter().enumerate() {
-            let filtered_achievements = self
-                .achievements
+    /// Pins `id` to the day at `date`, replacing any earlier pin of it.
+    /// Takes effect on the next [`ProgressTracker::update`].
+    pub fn pin(&mut self, id: &str, date: DateTime<Local>) {
+        self.pins.retain(|pin| pin.id != id);
+        self.pins.push(Pin {
+            id: id.to_string(),
+            date,
+        });
+    }
+
+    pub fn unpin(&mut self, id: &str) {
+        self.pins.retain(|pin| pin.id != id);
+    }
+
+    pub fn is_pinned(&self, id: &str, date: DateTime<Local>) -> bool {
+        self.pins.iter().any(|pin| pin.id == id && pin.date == date)
+    }
+
+    /// Moves the pins on the day at `from` to the day at `to`, e.g. when a lab was moved.
+    pub fn move_pins(&mut self, from: DateTime<Local>, to: DateTime<Local>) {
+        for pin in self.pins.iter_mut().filter(|pin| pin.date == from) {
+            pin.date = to;
+        }
+    }
+
+    /// Moves pins to days without a session that can take the achievement any more to
+    /// the next day that can, or the last one before if there is none after.
+    /// Pins that fit no session are kept as they are, in case the calendar changes back.
+    pub fn remap_pins(&mut self, course: &Course, achievements: &[Achievement]) {
+        let sessions = course.sessions(self.max_per_lab);
+        for pin in self.pins.iter_mut() {
+            let Some(achievement) = achievements
+                .iter()
+                .find(|achievement| achievement.id == pin.id)
+            else {
+                continue;
+            };
+            let dates = sessions
                 .iter()
-                .filter(|achievement| {
-                    achievement.presenting_type
-                        == AchievementPresention::Single(PresentationType::Lab)
+                .filter(|session| {
+                    fits(&achievement.presenting_type, &session.presentation)
+                        && achievement.sprint.to_date(course) <= session.date
                 })
-                .filter(|achievement| achievement.sprint.to_date() <= lab.date)
-                .filter(|achievement| achievement.grade <= self.target_grade)
-                .collect::<Vec<&Achievement>>()
-                .len() as u8;
+                .map(|session| session.date)
+                .collect::<Vec<DateTime<Local>>>();
+            if dates.contains(&pin.date) {
+                continue;
+            }
+            let remapped = dates
+                .iter()
+                .find(|date| **date > pin.date)
+                .or_else(|| dates.last());
+            if let Some(date) = remapped {
+                pin.date = *date;
+            }
+        }
+    }
+
+    /// Reads the pins from `pins.toml` in `path`. Without one there are none.
+    pub fn load_pins(&mut self, path: &Path) {
+        let file = path.join(PINS_FILE);
+        let Ok(contents) = std::fs::read_to_string(&file) else {
+            return;
+        };
+        let pins = toml::from_str::<SerializablePins>(&contents)
+            .map_err(CourseError::Toml)
+            .and_then(|pins| {
+                pins.pins
+                    .into_iter()
+                    .map(|pin| {
+                        Ok(Pin {
+                            id: pin.id,
+                            date: course::parse_date(&pin.date)?,
+                        })
+                    })
+                    .collect::<Result<Vec<Pin>, CourseError>>()
+            });
+        match pins {
+            Ok(pins) => self.pins = pins,
+            Err(e) => eprintln!("Error reading {}: {}", file.display(), e),
+        }
+    }
 
-            let achievements_left = self
-                .achievements
+    /// Writes the pins to `pins.toml` in `path`.
+    pub fn save_pins(&self, path: &Path) -> Result<(), CourseError> {
+        let pins = SerializablePins {
+            pins: self
+                .pins
                 .iter()
-                .filter(|achievement| {
-                    achievement.presenting_type
-                        == AchievementPresention::Single(PresentationType::Lab)
+                .map(|pin| SerializablePin {
+                    id: pin.id.clone(),
+                    date: course::format_date(pin.date),
                 })
-                .filter(|achievement| achievement.sprint.to_date() <= lab.date)
-                .filter(|achievement| achievement.grade <= self.target_grade)
-                .filter(|achievement| !achievement.done)
-                .collect::<Vec<&Achievement>>()
-                .len() as u8;
-
-            match self.mode {
-                ProgressTrackerMode::Left => {
-                    self.optimal -= u8::min(
-                        self.max_per_lab,
-                        filtered_achievements - (max_achievements - self.optimal),
-                    );
-                    self.minimum -= u8::min(
-                        self.max_per_lab,
-                        u8::min(
-                            filtered_achievements - (max_achievements - self.minimum),
-                            u8::div_ceil(
-                                max_achievements - (max_achievements - self.minimum),
-                                (self.labs.len() - i) as u8,
-                            ),
-                        ),
+                .collect(),
+        };
+        let contents = toml::to_string(&pins).map_err(CourseError::Serialize)?;
+        std::fs::write(path.join(PINS_FILE), contents).map_err(CourseError::Io)
+    }
+
+    /// Achievements that are not done and could be pinned to the day at `date`.
+    pub fn candidates<'a>(
+        &self,
+        course: &Course,
+        achievements: &'a [Achievement],
+        date: DateTime<Local>,
+    ) -> Vec<&'a Achievement> {
+        let sessions = course
+            .sessions(self.max_per_lab)
+            .into_iter()
+            .filter(|session| session.date == date)
+            .collect::<Vec<Session>>();
+        achievements
+            .iter()
+            .filter(|achievement| !achievement.done && achievement.grade <= self.target_grade)
+            .filter(|achievement| {
+                sessions.iter().any(|session| {
+                    fits(&achievement.presenting_type, &session.presentation)
+                        && achievement.sprint.to_date(course) <= session.date
+                })
+            })
+            .collect()
+    }
+
+    /// Which achievements that are not done to present at each of `sessions` from `now` on.
+    ///
+    /// Achievements pinned to a session's day go there first, even past its capacity.
+    /// The rest of the capacity goes to achievements that can only be presented in
+    /// that session's channel, then to `Either` ones, taking those marked
+    /// `present_soon` first and otherwise keeping the file order.
+    fn plan(
+        &self,
+        course: &Course,
+        sessions: &[Session],
+        achievements: &[&Achievement],
+        now: DateTime<Local>,
+    ) -> Vec<Vec<String>> {
+        let mut waiting = achievements
+            .iter()
+            .filter(|achievement| !achievement.done)
+            .copied()
+            .collect::<Vec<&Achievement>>();
+        // Stable, so achievements keep the file order otherwise.
+        waiting.sort_by_key(|achievement| !achievement.present_soon);
+
+        let can_present = |achievement: &Achievement, session: &Session| {
+            fits(&achievement.presenting_type, &session.presentation)
+                && achievement.sprint.to_date(course) <= session.date
+        };
+        // Pins to days that have passed or cannot take the achievement are ignored.
+        let pinned_to = |achievement: &Achievement| {
+            self.pins
+                .iter()
+                .find(|pin| pin.id == achievement.id && pin.date >= now)
+                .map(|pin| pin.date)
+                .filter(|date| {
+                    sessions
+                        .iter()
+                        .any(|session| session.date == *date && can_present(achievement, session))
+                })
+        };
+        let mut planned = vec![false; waiting.len()];
+
+        let mut result = Vec::with_capacity(sessions.len());
+        for session in sessions.iter() {
+            let mut ids = Vec::new();
+            if session.date < now {
+                result.push(ids);
+                continue;
+            }
+            let can_present = |achievement: &Achievement| can_present(achievement, session);
+
+            let mut quota = session.capacity as usize;
+            for (achievement, planned) in waiting.iter().zip(planned.iter_mut()) {
+                if !*planned
+                    && pinned_to(achievement) == Some(session.date)
+                    && can_present(achievement)
+                {
+                    *planned = true;
+                    quota = quota.saturating_sub(1);
+                    ids.push(achievement.id.clone());
+                }
+            }
+
+            for only_here in [true, false] {
+                for (achievement, planned) in waiting.iter().zip(planned.iter_mut()) {
+                    if quota == 0 {
+                        break;
+                    }
+                    let single = matches!(
+                        achievement.presenting_type,
+                        AchievementPresention::Single(_)
                     );
-                    if Local::now() > lab.date {
-                        self.target -= u8::min(
-                            4,
-                            u8::min(
-                                filtered_achievements - (max_achievements - self.target),
-                                (filtered_achievements - achievements_left)
-                                    - (max_achievements - self.target),
-                            ),
-                        );
-                        self.current_minimum -= u8::min(
-                            4,
-                            u8::min(
-                                filtered_achievements - (max_achievements - self.current_minimum),
-                                (filtered_achievements - achievements_left)
-                                    - (max_achievements - self.current_minimum),
-                            ),
-                        );
-                    } else {
-                        self.target -= u8::min(
-                            self.max_per_lab,
-                            filtered_achievements - (max_achievements - self.target),
-                        );
-                        self.current_minimum -= u8::min(
-                            self.max_per_lab,
-                            u8::min(
-                                filtered_achievements - (max_achievements - self.current_minimum),
-                                u8::div_ceil(
-                                    max_achievements - (max_achievements - self.current_minimum),
-                                    (self.labs.len() - i) as u8,
-                                ),
-                            ),
-                        );
+                    if !*planned
+                        && single == only_here
+                        && pinned_to(achievement).is_none()
+                        && can_present(achievement)
+                    {
+                        *planned = true;
+                        quota -= 1;
+                        ids.push(achievement.id.clone());
                     }
                 }
-                ProgressTrackerMode::Done => {
-                    self.optimal += u8::min(self.max_per_lab, filtered_achievements - self.optimal);
-                    self.minimum += u8::min(
-                        self.max_per_lab,
-                        u8::min(
-                            filtered_achievements - self.minimum,
-                            u8::div_ceil(
-                                max_achievements - self.minimum,
-                                (self.labs.len() - i) as u8,
-                            ),
-                        ),
+            }
+
+            result.push(ids);
+        }
+
+        result
+    }
+
+    /// Walks through `sessions` presenting achievements at `pace`
+    /// and returns how many are left after each session.
+    ///
+    /// Sessions before `actual_until` only present achievements that are done,
+    /// reflecting progress so far instead of a plan.
+    /// `Either` achievements fill whatever capacity is left once achievements
+    /// that can only go to that session's channel have been scheduled.
+    fn schedule(
+        course: &Course,
+        sessions: &[Session],
+        achievements: &[&Achievement],
+        pace: Pace,
+        actual_until: Option<DateTime<Local>>,
+    ) -> Vec<usize> {
+        let mut presented = vec![false; achievements.len()];
+        let mut left = achievements.len();
+        let mut result = Vec::with_capacity(sessions.len());
+
+        for (i, session) in sessions.iter().enumerate() {
+            let actual = actual_until.is_some_and(|now| session.date < now);
+            let can_present = |achievement: &Achievement| {
+                fits(&achievement.presenting_type, &session.presentation)
+                    && achievement.sprint.to_date(course) <= session.date
+                    && (!actual || achievement.done)
+            };
+
+            let mut quota = session.capacity as usize;
+            if pace == Pace::Even && !actual {
+                let waiting = achievements
+                    .iter()
+                    .zip(presented.iter())
+                    .filter(|(achievement, presented)| {
+                        !**presented && fits(&achievement.presenting_type, &session.presentation)
+                    })
+                    .count();
+                let sessions_left = sessions[i..]
+                    .iter()
+                    .filter(|later| later.presentation == session.presentation)
+                    .count();
+                quota = quota.min(waiting.div_ceil(sessions_left.max(1)));
+            }
+
+            // Achievements with only this channel first, `Either` ones can still go elsewhere.
+            for only_here in [true, false] {
+                for (achievement, presented) in achievements.iter().zip(presented.iter_mut()) {
+                    if quota == 0 {
+                        break;
+                    }
+                    let single = matches!(
+                        achievement.presenting_type,
+                        AchievementPresention::Single(_)
                     );
-                    if Local::now() > lab.date {
-                        self.target += u8::min(
-                            4,
-                            u8::min(
-                                filtered_achievements - self.target,
-                                (filtered_achievements - achievements_left) - self.target,
-                            ),
-                        );
-                        self.current_minimum += u8::min(
-                            4,
-                            u8::min(
-                                filtered_achievements - self.current_minimum,
-                                (filtered_achievements - achievements_left) - self.current_minimum,
-                            ),
-                        );
-                    } else {
-                        self.target +=
-                            u8::min(self.max_per_lab, filtered_achievements - self.target);
-                        self.current_minimum += u8::min(
-                            self.max_per_lab,
-                            u8::min(
-                                filtered_achievements - self.current_minimum,
-                                u8::div_ceil(
-                                    max_achievements - self.current_minimum,
-                                    (self.labs.len() - i) as u8,
-                                ),
-                            ),
-                        );
+                    if !*presented && single == only_here && can_present(achievement) {
+                        *presented = true;
+                        quota -= 1;
+                        left -= 1;
                     }
                 }
             }
 
-            self.labs[i].optimal = self.optimal;
-            self.labs[i].minimum = self.minimum;
-            self.labs[i].target = self.target;
-            self.labs[i].current_minimum = self.current_minimum;
+            result.push(left);
+        }
+
+        result
+    }
+}
+
+/// `count` as a `u8`, saturating instead of wrapping.
+fn clamp(count: usize) -> u8 {
+    u8::try_from(count).unwrap_or(u8::MAX)
+}
+
+/// Whether an achievement presented as `presenting_type` can be presented at a `session`.
+fn fits(presenting_type: &AchievementPresention, session: &PresentationType) -> bool {
+    match presenting_type {
+        AchievementPresention::Single(presentation) => presentation == session,
+        AchievementPresention::Either { first, second } => first == session || second == session,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::{AchievementLanguage, ProgrammingLanguage, Sprint};
+    use crate::course::{Channel, Lab};
+    use chrono::{Duration, NaiveDate};
+    use proptest::prelude::*;
+
+    fn achievement(id: &str, present_soon: bool) -> Achievement {
+        Achievement {
+            id: id.to_string(),
+            title: String::new(),
+            deadline: None,
+            done: false,
+            present_soon,
+            grade: 3,
+            presenting_type: AchievementPresention::Single(PresentationType::Lab),
+            programming_language: AchievementLanguage::Single(ProgrammingLanguage::C),
+            sprint: Sprint::Sprint1,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn plan_prefers_pinned_and_planned_achievements() {
+        let today = course::from_day(NaiveDate::from_ymd_opt(2100, 1, 4).unwrap());
+        let labs = [today + Duration::days(1), today + Duration::days(2)];
+        let course = Course {
+            name: String::new(),
+            link_template: String::new(),
+            labs: labs.iter().copied().map(Lab::new).collect(),
+            sprints: vec![(Sprint::Sprint1, today - Duration::days(7))],
+            channels: Vec::new(),
+            grade_rules: Vec::new(),
+            achievements: Vec::new(),
+        };
+        let achievements = [
+            achievement("A1", false),
+            achievement("A2", false),
+            achievement("A3", true),
+        ];
+
+        let mut tracker =
+            ProgressTracker::new(2, 5, ProgressTrackerMode::Left, &course, &achievements);
+        assert_eq!(tracker.days[0].plan, ["A3", "A1"]);
+        assert_eq!(tracker.days[1].plan, ["A2"]);
+
+        tracker.pin("A2", labs[0]);
+        tracker.update(&course, &achievements);
+        assert_eq!(tracker.days[0].plan, ["A2", "A3"]);
+        assert_eq!(tracker.days[1].plan, ["A1"]);
+
+        tracker.unpin("A2");
+        tracker.update(&course, &achievements);
+        assert_eq!(tracker.days[0].plan, ["A3", "A1"]);
+    }
+
+    const PRESENTATIONS: [&str; 5] = ["Lab", "Studium", "Special", "Lab, Studium", "Report"];
+    const SPRINTS: [Sprint; 5] = [
+        Sprint::Sprint1,
+        Sprint::Sprint2,
+        Sprint::Sprint3,
+        Sprint::Sprint4,
+        Sprint::Project,
+    ];
+
+    /// A day around today, some of the course has to be over and some still to come.
+    fn day(offset: i64) -> DateTime<Local> {
+        course::from_day(Local::now().date_naive() + Duration::days(offset))
+    }
+
+    fn arbitrary_achievement() -> impl Strategy<Value = Achievement> {
+        (3..=5i8, any::<bool>(), any::<bool>(), 0..5usize, 0..5usize).prop_map(
+            |(grade, done, present_soon, presentation, sprint)| Achievement {
+                grade,
+                done,
+                presenting_type: AchievementPresention::from_string(PRESENTATIONS[presentation])
+                    .unwrap(),
+                sprint: SPRINTS[sprint].clone(),
+                ..achievement("", present_soon)
+            },
+        )
+    }
+
+    fn arbitrary_course() -> impl Strategy<Value = Course> {
+        (
+            prop::collection::vec((-60..60i64, any::<bool>()), 0..12),
+            prop::collection::vec(-60..60i64, 0..5),
+            prop::option::of((0..4u8, prop::collection::vec(-60..60i64, 0..6))),
+        )
+            .prop_map(|(labs, sprints, studium)| Course {
+                name: String::new(),
+                link_template: String::new(),
+                labs: labs
+                    .into_iter()
+                    .map(|(offset, cancelled)| Lab {
+                        cancelled,
+                        ..Lab::new(day(offset))
+                    })
+                    .collect(),
+                sprints: SPRINTS
+                    .iter()
+                    .cloned()
+                    .zip(sprints.into_iter().map(day))
+                    .collect(),
+                channels: studium
+                    .into_iter()
+                    .map(|(capacity, sessions)| Channel {
+                        presentation: PresentationType::Studium,
+                        capacity,
+                        sessions: sessions.into_iter().map(day).collect(),
+                    })
+                    .collect(),
+                grade_rules: Vec::new(),
+                achievements: Vec::new(),
+            })
+    }
+
+    fn arbitrary_tracker() -> impl Strategy<Value = (ProgressTracker, Course, Vec<Achievement>)> {
+        (
+            prop::collection::vec(arbitrary_achievement(), 0..40),
+            arbitrary_course(),
+            0..6u8,
+            0..=6i8,
+        )
+            .prop_map(|(achievements, course, max_per_lab, target_grade)| {
+                let achievements = achievements
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, achievement)| Achievement {
+                        id: format!("A{}", i + 1),
+                        ..achievement
+                    })
+                    .collect::<Vec<Achievement>>();
+                let tracker = ProgressTracker::new(
+                    max_per_lab,
+                    target_grade,
+                    ProgressTrackerMode::Left,
+                    &course,
+                    &achievements,
+                );
+                (tracker, course, achievements)
+            })
+    }
+
+    fn columns(tracker: &ProgressTracker) -> [Vec<u8>; 4] {
+        [
+            tracker.days.iter().map(|day| day.optimal).collect(),
+            tracker.days.iter().map(|day| day.minimum).collect(),
+            tracker.days.iter().map(|day| day.target).collect(),
+            tracker.days.iter().map(|day| day.current_minimum).collect(),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn achievements_left_never_grow_or_leave_range(
+            (tracker, _, achievements) in arbitrary_tracker()
+        ) {
+            let total = achievements
+                .iter()
+                .filter(|achievement| achievement.grade <= tracker.target_grade)
+                .count() as u8;
+            let totals = [tracker.optimal, tracker.minimum, tracker.target, tracker.current_minimum];
+
+            for (column, last) in columns(&tracker).iter().zip(totals) {
+                prop_assert!(column.iter().all(|left| *left <= total));
+                prop_assert!(column.windows(2).all(|pair| pair[0] >= pair[1]));
+                prop_assert_eq!(column.last().copied().unwrap_or(total), last);
+            }
+            for (optimal, minimum) in tracker.days.iter().map(|day| (day.optimal, day.minimum)) {
+                prop_assert!(optimal <= minimum);
+            }
+        }
+
+        #[test]
+        fn achievements_done_mirror_achievements_left(
+            (mut tracker, course, achievements) in arbitrary_tracker()
+        ) {
+            let total = achievements
+                .iter()
+                .filter(|achievement| achievement.grade <= tracker.target_grade)
+                .count() as u8;
+            let left = columns(&tracker);
+
+            tracker.mode = tracker.mode.toggle();
+            tracker.changed(Change::Parameters, &course, &achievements);
+            let done = columns(&tracker);
+
+            for (left, done) in left.iter().zip(done.iter()) {
+                prop_assert!(done.windows(2).all(|pair| pair[0] <= pair[1]));
+                for (left, done) in left.iter().zip(done.iter()) {
+                    prop_assert_eq!(left + done, total);
+                }
+            }
+        }
+
+        #[test]
+        fn plan_suggests_each_waiting_achievement_at_most_once(
+            (tracker, course, achievements) in arbitrary_tracker()
+        ) {
+            let now = Local::now();
+            let mut suggested = Vec::new();
+            for day in tracker.days.iter() {
+                if day.date < now {
+                    prop_assert!(day.plan.is_empty());
+                }
+                for id in day.plan.iter() {
+                    let achievement = achievements
+                        .iter()
+                        .find(|achievement| &achievement.id == id)
+                        .unwrap();
+                    prop_assert!(!achievement.done);
+                    prop_assert!(achievement.grade <= tracker.target_grade);
+                    prop_assert!(achievement.sprint.to_date(&course) <= day.date);
+                    prop_assert!(!suggested.contains(id));
+                    suggested.push(id.clone());
+                }
+            }
         }
     }
 }
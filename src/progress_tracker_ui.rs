@@ -1,15 +1,45 @@
-use crate::achievements::Sprint;
 use crate::application::Application;
-use crate::progress_tracker;
+use achievements_enhanced::achievements::{PresentationType, Sprint};
+use achievements_enhanced::course::Lab;
+use achievements_enhanced::progress_tracker::{self, Day};
+use achievements_enhanced::simulation::Simulation;
 
 use chrono::Local;
 use eframe::egui;
 
 impl Application {
     pub fn progress_tracker_ui(&mut self, ctx: &egui::Context) {
+        if self.simulation.is_some() {
+            self.simulation_ui(ctx);
+        }
+        if self.editing_calendar {
+            self.calendar_ui(ctx);
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
             self.menu_bar(ctx, ui);
 
+            ui.horizontal(|ui| {
+                let what_if = self.simulation.is_some();
+                if ui
+                    .selectable_label(what_if, self.language.what_if.clone())
+                    .clicked()
+                {
+                    self.simulation = match what_if {
+                        true => None,
+                        false => Some(Simulation::new(self.workspace())),
+                    };
+                }
+                if ui
+                    .selectable_label(self.editing_calendar, self.language.edit_labs.clone())
+                    .clicked()
+                {
+                    self.editing_calendar = !self.editing_calendar;
+                }
+                if what_if {
+                    ui.label(self.language.what_if_hint.clone());
+                }
+            });
+
             egui::ScrollArea::both()
                 .stick_to_right(true)
                 .show(ui, |ui| {
@@ -18,7 +48,7 @@ impl Application {
                             self.settings.font_size * 5.0,
                             self.settings.font_size * 2.0,
                         ))
-                        .num_columns(7)
+                        .num_columns(8)
                         .striped(true)
                         .show(ui, |ui| {
                             let lab = self
@@ -40,7 +70,7 @@ impl Application {
                                 .heading(ui, self.language.optimal.clone())
                                 .on_hover_cursor(egui::CursorIcon::PointingHand)
                                 .on_hover_ui(|ui| {
-                                    ui.label(match self.progress_tracker.mode {
+                                    ui.label(match self.settings.progress_tracker_mode {
                                         progress_tracker::ProgressTrackerMode::Left => {
                                             self.language.click_to_show_achievements_done.clone()
                                         }
@@ -51,15 +81,16 @@ impl Application {
                                 });
 
                             if optimal.clicked() {
-                                self.progress_tracker.mode = self.progress_tracker.mode.toggle();
-                                self.progress_tracker.update();
+                                self.settings.progress_tracker_mode =
+                                    self.settings.progress_tracker_mode.toggle();
+                                self.configure_progress();
                             }
 
                             let minimum = self
                                 .heading(ui, self.language.minimum.clone())
                                 .on_hover_cursor(egui::CursorIcon::PointingHand)
                                 .on_hover_ui(|ui| {
-                                    ui.label(match self.progress_tracker.mode {
+                                    ui.label(match self.settings.progress_tracker_mode {
                                         progress_tracker::ProgressTrackerMode::Left => {
                                             self.language.click_to_show_achievements_done.clone()
                                         }
@@ -70,15 +101,16 @@ impl Application {
                                 });
 
                             if minimum.clicked() {
-                                self.progress_tracker.mode = self.progress_tracker.mode.toggle();
-                                self.progress_tracker.update();
+                                self.settings.progress_tracker_mode =
+                                    self.settings.progress_tracker_mode.toggle();
+                                self.configure_progress();
                             }
 
                             let target = self
                                 .heading(ui, self.language.target.clone())
                                 .on_hover_cursor(egui::CursorIcon::PointingHand)
                                 .on_hover_ui(|ui| {
-                                    ui.label(match self.progress_tracker.mode {
+                                    ui.label(match self.settings.progress_tracker_mode {
                                         progress_tracker::ProgressTrackerMode::Left => {
                                             self.language.click_to_show_achievements_done.clone()
                                         }
@@ -89,15 +121,16 @@ impl Application {
                                 });
 
                             if target.clicked() {
-                                self.progress_tracker.mode = self.progress_tracker.mode.toggle();
-                                self.progress_tracker.update();
+                                self.settings.progress_tracker_mode =
+                                    self.settings.progress_tracker_mode.toggle();
+                                self.configure_progress();
                             }
 
                             let current_minimum = self
                                 .heading(ui, self.language.minimum_to_reach_target_grade.clone())
                                 .on_hover_cursor(egui::CursorIcon::PointingHand)
                                 .on_hover_ui(|ui| {
-                                    ui.label(match self.progress_tracker.mode {
+                                    ui.label(match self.settings.progress_tracker_mode {
                                         progress_tracker::ProgressTrackerMode::Left => {
                                             self.language.click_to_show_achievements_done.clone()
                                         }
@@ -108,62 +141,138 @@ impl Application {
                                 });
 
                             if current_minimum.clicked() {
-                                self.progress_tracker.mode = self.progress_tracker.mode.toggle();
-                                self.progress_tracker.update();
+                                self.settings.progress_tracker_mode =
+                                    self.settings.progress_tracker_mode.toggle();
+                                self.configure_progress();
                             }
 
+                            ui.heading(self.language.plan.clone());
                             ui.allocate_space(egui::vec2(0.0, 0.0));
                             ui.end_row();
-                            for (i, lab) in
-                                self.progress_tracker.labs.clone().into_iter().enumerate()
-                            {
-                                if Local::now() <= lab.date || self.settings.show_passed_labs {
-                                    ui.label(format!("{} {}", self.language.lab, i + 1));
-                                    ui.label(lab.date.format("%a %b %d, %Y").to_string());
-                                    ui.label(lab.optimal.to_string());
-                                    ui.label(lab.minimum.to_string());
-                                    ui.label(lab.target.to_string());
-                                    ui.label(lab.current_minimum.to_string());
+                            let mut cancelled = self
+                                .workspace()
+                                .course
+                                .labs
+                                .iter()
+                                .filter(|lab| lab.cancelled)
+                                .cloned()
+                                .collect::<Vec<Lab>>()
+                                .into_iter()
+                                .peekable();
+                            for day in self.workspace().progress_tracker.days.clone() {
+                                while let Some(lab) = cancelled.next_if(|lab| lab.date <= day.date)
+                                {
+                                    self.cancelled_lab_ui(ui, &lab);
+                                }
+                                if Local::now() <= day.date || self.settings.show_passed_labs {
+                                    let label = self
+                                        .workspace()
+                                        .course
+                                        .labs
+                                        .iter()
+                                        .find(|lab| lab.date == day.date && !lab.cancelled)
+                                        .and_then(|lab| lab.label.clone());
+                                    let presentations = day
+                                        .presentations
+                                        .iter()
+                                        .map(|presentation| match presentation {
+                                            PresentationType::Lab => self.language.lab.clone(),
+                                            PresentationType::Studium => {
+                                                self.language.studium.clone()
+                                            }
+                                            PresentationType::Special => {
+                                                self.language.special.clone()
+                                            }
+                                            PresentationType::Report => {
+                                                self.language.report.clone()
+                                            }
+                                        })
+                                        .collect::<Vec<String>>()
+                                        .join(", ");
+                                    ui.label(match label {
+                                        Some(label) => format!("{} ({})", presentations, label),
+                                        None => presentations,
+                                    });
+                                    ui.label(day.date.format("%a %b %d, %Y").to_string());
+                                    let simulated = self.simulation.as_ref().map(|simulation| {
+                                        simulation
+                                            .progress_tracker
+                                            .days
+                                            .iter()
+                                            .find(|simulated| simulated.date == day.date)
+                                            .cloned()
+                                    });
+                                    let value = |field: fn(&Day) -> u8| {
+                                        compare(
+                                            field(&day),
+                                            simulated.as_ref().map(|day| day.as_ref().map(field)),
+                                        )
+                                    };
+                                    ui.label(value(|day| day.optimal));
+                                    ui.label(value(|day| day.minimum));
+                                    ui.label(value(|day| day.target));
+                                    ui.label(value(|day| day.current_minimum));
+                                    self.plan_ui(ui, &day);
                                     ui.end_row();
                                 }
                             }
+                            for lab in cancelled {
+                                self.cancelled_lab_ui(ui, &lab);
+                            }
                         });
                     ui.allocate_space(egui::vec2(0.0, 50.0));
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.grade3,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.grade == 3 && e.done)
                             .count(),
-                        self.achievements.iter().filter(|e| e.grade == 3).count(),
+                        self.workspace()
+                            .achievements
+                            .iter()
+                            .filter(|e| e.grade == 3)
+                            .count(),
                     ));
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.grade4,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.grade == 4 && e.done)
                             .count(),
-                        self.achievements.iter().filter(|e| e.grade == 4).count(),
+                        self.workspace()
+                            .achievements
+                            .iter()
+                            .filter(|e| e.grade == 4)
+                            .count(),
                     ));
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.grade5,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.grade == 5 && e.done)
                             .count(),
-                        self.achievements.iter().filter(|e| e.grade == 5).count(),
+                        self.workspace()
+                            .achievements
+                            .iter()
+                            .filter(|e| e.grade == 5)
+                            .count(),
                     ));
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.sprint1,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint1 && e.done)
                             .count(),
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint1)
                             .count(),
@@ -171,11 +280,13 @@ impl Application {
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.sprint2,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint2 && e.done)
                             .count(),
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint2)
                             .count(),
@@ -183,11 +294,13 @@ impl Application {
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.sprint3,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint3 && e.done)
                             .count(),
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint3)
                             .count(),
@@ -195,11 +308,13 @@ impl Application {
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.sprint4,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint4 && e.done)
                             .count(),
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Sprint4)
                             .count(),
@@ -207,11 +322,13 @@ impl Application {
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.project,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Project && e.done)
                             .count(),
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Project)
                             .count(),
@@ -219,12 +336,14 @@ impl Application {
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.other,
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| (e.sprint == Sprint::Unclear || e.sprint == Sprint::Lab)
                                 && e.done)
                             .count(),
-                        self.achievements
+                        self.workspace()
+                            .achievements
                             .iter()
                             .filter(|e| e.sprint == Sprint::Unclear || e.sprint == Sprint::Lab)
                             .count(),
@@ -232,10 +351,202 @@ impl Application {
                     ui.label(format!(
                         "{}: {}/{}",
                         self.language.total,
-                        self.achievements.iter().filter(|e| e.done).count(),
-                        self.achievements.len(),
+                        self.workspace()
+                            .achievements
+                            .iter()
+                            .filter(|e| e.done)
+                            .count(),
+                        self.workspace().achievements.len(),
                     ));
+                    ui.allocate_space(egui::vec2(0.0, 20.0));
+                    self.grades_ui(ui);
+                });
+        });
+    }
+
+    /// Controls for the what-if simulation, next to the progress tracker.
+    fn simulation_ui(&mut self, ctx: &egui::Context) {
+        egui::SidePanel::right("What If Panel").show(ctx, |ui| {
+            let grades = self.workspace().course.grade_range();
+            let Some(simulation) = self.simulation.as_mut() else {
+                return;
+            };
+            ui.horizontal(|ui| {
+                ui.heading(self.language.what_if.clone());
+                if ui.button(self.language.reset.clone()).clicked() {
+                    *simulation = Simulation::new(&self.workspaces[self.active_workspace]);
+                }
+            });
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("What If Grid").show(ui, |ui| {
+                    ui.label(self.language.target_grade.clone());
+                    let mut target_grade = simulation.progress_tracker.target_grade;
+                    if ui
+                        .add(egui::Slider::new(&mut target_grade, grades))
+                        .changed()
+                    {
+                        simulation.set_target_grade(target_grade);
+                    }
+                    ui.end_row();
+                    ui.label(self.language.max_per_lab.clone());
+                    let mut max_per_lab = simulation.progress_tracker.max_per_lab;
+                    if ui.add(egui::Slider::new(&mut max_per_lab, 1..=4)).changed() {
+                        simulation.set_max_per_lab(max_per_lab);
+                    }
+                    ui.end_row();
                 });
+
+                ui.separator();
+                ui.strong(self.language.labs.clone());
+                for lab in simulation.course.labs.clone() {
+                    let mut kept = !lab.cancelled;
+                    if ui
+                        .checkbox(&mut kept, lab.date.format("%a %b %d, %Y").to_string())
+                        .changed()
+                    {
+                        simulation.toggle_lab(lab.date);
+                    }
+                }
+
+                ui.separator();
+                ui.strong(self.language.done.clone());
+                let real = &self.workspaces[self.active_workspace].achievements;
+                let achievements = simulation
+                    .achievements
+                    .iter()
+                    .filter(|achievement| {
+                        real.iter()
+                            .any(|real| real.id == achievement.id && !real.done)
+                    })
+                    .map(|achievement| {
+                        (
+                            achievement.id.clone(),
+                            achievement.title.clone(),
+                            achievement.done,
+                        )
+                    })
+                    .collect::<Vec<(String, String, bool)>>();
+                for (id, title, mut done) in achievements {
+                    if ui
+                        .checkbox(&mut done, format!("{} {}", id, title))
+                        .changed()
+                    {
+                        simulation.set_done(&id, done);
+                    }
+                }
+            });
+        });
+    }
+
+    /// A row for a cancelled lab, struck out.
+    fn cancelled_lab_ui(&mut self, ui: &mut egui::Ui, lab: &Lab) {
+        if Local::now() > lab.date && !self.settings.show_passed_labs {
+            return;
+        }
+        let text = match &lab.label {
+            Some(label) => format!("{} ({})", self.language.lab, label),
+            None => self.language.lab.clone(),
+        };
+        ui.label(egui::RichText::new(text).strikethrough().weak())
+            .on_hover_text(self.language.cancelled.clone());
+        ui.label(
+            egui::RichText::new(lab.date.format("%a %b %d, %Y").to_string())
+                .strikethrough()
+                .weak(),
+        );
+        ui.end_row();
+    }
+
+    /// The achievements suggested for `day`. Clicking one pins it to the day or unpins it.
+    fn plan_ui(&mut self, ui: &mut egui::Ui, day: &Day) {
+        ui.horizontal_wrapped(|ui| {
+            for id in day.plan.iter() {
+                let pinned = self.workspace().progress_tracker.is_pinned(id, day.date);
+                let label = ui.selectable_label(pinned, id).on_hover_ui(|ui| {
+                    if let Some(achievement) = self.workspace().get(id) {
+                        ui.label(&achievement.title);
+                    }
+                    ui.label(match pinned {
+                        false => self.language.click_to_pin.clone(),
+                        true => self.language.click_to_unpin.clone(),
+                    });
+                });
+                if label.clicked() {
+                    let result = match pinned {
+                        false => self.workspace_mut().pin(id, day.date),
+                        true => self.workspace_mut().unpin(id),
+                    };
+                    if let Err(e) = result {
+                        eprintln!("Error saving pins: {}", e);
+                    }
+                }
+            }
+
+            if Local::now() <= day.date {
+                let candidates = self
+                    .workspace()
+                    .pin_candidates(day.date)
+                    .into_iter()
+                    .filter(|achievement| !day.plan.contains(&achievement.id))
+                    .map(|achievement| (achievement.id.clone(), achievement.title.clone()))
+                    .collect::<Vec<(String, String)>>();
+                ui.menu_button("+", |ui| {
+                    ui.label(self.language.pin_achievement.clone());
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for (id, title) in candidates {
+                            if ui.button(format!("{} {}", id, title)).clicked() {
+                                if let Err(e) = self.workspace_mut().pin(&id, day.date) {
+                                    eprintln!("Error saving pins: {}", e);
+                                }
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                });
+            }
         });
     }
+
+    /// The grade reached and what the next one takes according to the course's grade rules.
+    fn grades_ui(&mut self, ui: &mut egui::Ui) {
+        let grades = self.workspace().progress_tracker.grades.clone();
+        let grade = |reached: Option<i8>| match reached {
+            Some(grade) => grade.to_string(),
+            None => self.language.no_grade_yet.clone(),
+        };
+        ui.label(match &self.simulation {
+            None => format!("{}: {}", self.language.grade_reached, grade(grades.reached)),
+            Some(simulation) => format!(
+                "{}: {} → {}",
+                self.language.grade_reached,
+                grade(grades.reached),
+                grade(simulation.progress_tracker.grades.reached)
+            ),
+        });
+        if let Some(next) = grades.next {
+            ui.label(format!("{} {}:", self.language.missing_for_grade, next));
+            for missing in grades.missing.iter() {
+                ui.label(format!(
+                    "    {} {}/{}",
+                    missing.requirement, missing.done, missing.required
+                ));
+            }
+            ui.label(format!(
+                "{}: {}",
+                self.language.cheapest_path,
+                grades.cheapest.join(", ")
+            ));
+        }
+    }
+}
+
+/// `real`, followed by the simulated value if a simulation is open.
+/// A day that the simulation took out shows a dash.
+fn compare(real: u8, simulated: Option<Option<u8>>) -> String {
+    match simulated {
+        None => real.to_string(),
+        Some(Some(simulated)) => format!("{} → {}", real, simulated),
+        Some(None) => format!("{} → –", real),
+    }
 }
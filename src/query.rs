@@ -0,0 +1,598 @@
+use crate::achievements::{Achievement, AchievementLanguage, AchievementPresention, Sprint};
+use crate::course;
+
+use chrono::{DateTime, Local, NaiveDate};
+
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterType {
+    Remove,
+    Include,
+}
+
+/// Values of one field to keep, or to leave out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter<T> {
+    pub typ: FilterType,
+    pub value: Vec<T>,
+}
+
+impl<T: std::cmp::PartialEq> Filter<T> {
+    pub fn new() -> Self {
+        Self {
+            typ: FilterType::Remove,
+            value: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.value.push(value);
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.value.contains(value)
+    }
+
+    pub fn matches(&self, value: &T) -> bool {
+        (self.typ == FilterType::Include) == self.contains(value)
+    }
+
+    /// Keeps only values that were included, adding `value` to them.
+    pub fn include(&mut self, value: T) {
+        if self.typ == FilterType::Remove {
+            self.typ = FilterType::Include;
+            self.value.clear();
+        }
+        if !self.contains(&value) {
+            self.push(value);
+        }
+    }
+
+    /// Leaves out `value`, whether other values were included or left out before.
+    pub fn remove(&mut self, value: T) {
+        match self.typ {
+            FilterType::Include => self.value.retain(|included| included != &value),
+            FilterType::Remove => {
+                if !self.contains(&value) {
+                    self.push(value);
+                }
+            }
+        }
+    }
+}
+
+impl<T: std::cmp::PartialEq> Default for Filter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a field compares to the value in a query, as in `grade>=4`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Less,
+    AtMost,
+    Equal,
+    AtLeast,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Less => ordering == Ordering::Less,
+            Comparison::AtMost => ordering != Ordering::Greater,
+            Comparison::Equal => ordering == Ordering::Equal,
+            Comparison::AtLeast => ordering != Ordering::Less,
+            Comparison::Greater => ordering == Ordering::Greater,
+        }
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Comparison::Less => write!(f, "<"),
+            Comparison::AtMost => write!(f, "<="),
+            Comparison::Equal => write!(f, "="),
+            Comparison::AtLeast => write!(f, ">="),
+            Comparison::Greater => write!(f, ">"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextField {
+    Id,
+    Title,
+    Comment,
+}
+
+impl std::fmt::Display for TextField {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TextField::Id => write!(f, "id"),
+            TextField::Title => write!(f, "title"),
+            TextField::Comment => write!(f, "comment"),
+        }
+    }
+}
+
+/// A condition from a query that is not a plain value of a field.
+/// Text is kept in lower case and compared ignoring case.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    /// The id, title or comment contains the text.
+    Text(String),
+    Contains(TextField, String),
+    Grade(Comparison, i8),
+    /// Achievements without a deadline never match.
+    Deadline(Comparison, NaiveDate),
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    pub fn matches(&self, achievement: &Achievement) -> bool {
+        match self {
+            Predicate::Text(text) => [TextField::Id, TextField::Title, TextField::Comment]
+                .into_iter()
+                .any(|field| Predicate::Contains(field, text.clone()).matches(achievement)),
+            Predicate::Contains(field, text) => {
+                let value = match field {
+                    TextField::Id => Some(&achievement.id),
+                    TextField::Title => Some(&achievement.title),
+                    TextField::Comment => achievement.comment.as_ref(),
+                };
+                value.is_some_and(|value| value.to_lowercase().contains(text))
+            }
+            Predicate::Grade(comparison, grade) => comparison.holds(achievement.grade.cmp(grade)),
+            Predicate::Deadline(comparison, date) => achievement
+                .deadline
+                .is_some_and(|deadline| comparison.holds(course::day(deadline).cmp(date))),
+            Predicate::Not(predicate) => !predicate.matches(achievement),
+        }
+    }
+}
+
+impl std::fmt::Display for Predicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            // Always quoted, a bare `done` or `soon` would read back as the flag.
+            Predicate::Text(text) => write!(f, "{}", quote(text)),
+            Predicate::Contains(field, text) => write!(f, "{}:{}", field, quoted(text)),
+            Predicate::Grade(comparison, grade) => write!(f, "grade{}{}", comparison, grade),
+            Predicate::Deadline(comparison, date) => {
+                write!(f, "deadline{}{}", comparison, date.format("%Y-%m-%d"))
+            }
+            Predicate::Not(predicate) => write!(f, "!{}", predicate),
+        }
+    }
+}
+
+/// A part of a query that could not be understood.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryError {
+    pub term: String,
+    pub reason: &'static str,
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.term, self.reason)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Which achievements to show. An achievement is shown if it passes every filter and predicate.
+///
+/// Filters can be written and read as a query such as `grade>=4 lang:C !done sprint:2 "pekare"`:
+///
+/// - A word or quoted text matches the id, title or comment.
+/// - `done` and `soon` match achievements that are done or planned to be presented soon.
+/// - `grade`, `type`, `lang` and `sprint` followed by `:` or `=` match a value.
+///   `grade` can also be compared with `<`, `<=`, `>=` and `>`.
+/// - `deadline` matches a date written as `2024-10-01` or `Oct 1, 2024`, or `none`,
+///   and can be compared the same way.
+/// - `id`, `title` and `comment` followed by `:` match text they contain,
+///   and followed by `=` the exact text.
+/// - `!` in front of a term leaves out what it matches.
+///
+/// Several values for the same field match any of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filters {
+    pub id: Filter<String>,
+    pub title: Filter<String>,
+    pub deadline: Filter<Option<DateTime<Local>>>,
+    pub done: Filter<bool>,
+    pub present_soon: Filter<bool>,
+    pub grade: Filter<i8>,
+    pub presenting_type: Filter<AchievementPresention>,
+    pub programming_language: Filter<AchievementLanguage>,
+    pub sprint: Filter<Sprint>,
+    pub comment: Filter<Option<String>>,
+    pub predicates: Vec<Predicate>,
+}
+
+impl Filters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn matches(&self, achievement: &Achievement) -> bool {
+        self.id.matches(&achievement.id)
+            && self.title.matches(&achievement.title)
+            && self.deadline.matches(&achievement.deadline)
+            && self.done.matches(&achievement.done)
+            && self.present_soon.matches(&achievement.present_soon)
+            && self.grade.matches(&achievement.grade)
+            && self.presenting_type.matches(&achievement.presenting_type)
+            && self
+                .programming_language
+                .matches(&achievement.programming_language)
+            && self.sprint.matches(&achievement.sprint)
+            && self.comment.matches(&achievement.comment)
+            && self
+                .predicates
+                .iter()
+                .all(|predicate| predicate.matches(achievement))
+    }
+
+    pub fn parse(query: &str) -> Result<Filters, QueryError> {
+        let mut filters = Filters::new();
+        for term in terms(query)? {
+            filters.add(&term)?;
+        }
+        Ok(filters)
+    }
+
+    fn add(&mut self, term: &str) -> Result<(), QueryError> {
+        let error = |reason| QueryError {
+            term: term.to_string(),
+            reason,
+        };
+        let (negated, rest) = match term.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, term),
+        };
+
+        if rest.starts_with('"') {
+            return self.predicate(negated, Predicate::Text(unquoted(rest).to_lowercase()));
+        }
+        let Some((key, comparison, value)) = split(rest) else {
+            return match rest.to_lowercase().as_str() {
+                "done" => {
+                    set(&mut self.done, negated, true);
+                    Ok(())
+                }
+                "soon" => {
+                    set(&mut self.present_soon, negated, true);
+                    Ok(())
+                }
+                _ => self.predicate(negated, Predicate::Text(rest.to_lowercase())),
+            };
+        };
+        let unquoted = unquoted(value);
+        let value = unquoted.as_str();
+        // `:` and `=` both pick a value, except for text where `:` looks inside it.
+        let exact = matches!(comparison, Comparison::Equal);
+        let contains = rest[key.len()..].starts_with(':');
+
+        match key.to_lowercase().as_str() {
+            "id" | "title" | "comment" if contains => {
+                let field = match key.to_lowercase().as_str() {
+                    "id" => TextField::Id,
+                    "title" => TextField::Title,
+                    _ => TextField::Comment,
+                };
+                self.predicate(negated, Predicate::Contains(field, value.to_lowercase()))
+            }
+            "id" if exact => {
+                set(&mut self.id, negated, value.to_string());
+                Ok(())
+            }
+            "title" if exact => {
+                set(&mut self.title, negated, value.to_string());
+                Ok(())
+            }
+            "comment" if exact => {
+                let comment = Some(value.to_string()).filter(|comment| comment != "none");
+                set(&mut self.comment, negated, comment);
+                Ok(())
+            }
+            "done" | "soon" if exact => {
+                let flag = match value.to_lowercase().as_str() {
+                    "true" | "yes" => true,
+                    "false" | "no" => false,
+                    _ => return Err(error("expected true or false")),
+                };
+                match key.to_lowercase().as_str() {
+                    "done" => set(&mut self.done, negated, flag),
+                    _ => set(&mut self.present_soon, negated, flag),
+                }
+                Ok(())
+            }
+            "grade" => {
+                let grade = value.parse::<i8>().map_err(|_| error("expected a grade"))?;
+                match exact {
+                    true => set(&mut self.grade, negated, grade),
+                    false => self.predicate(negated, Predicate::Grade(comparison, grade))?,
+                }
+                Ok(())
+            }
+            "deadline" => {
+                if exact && value.eq_ignore_ascii_case("none") {
+                    set(&mut self.deadline, negated, None);
+                    return Ok(());
+                }
+                let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .ok()
+                    .or_else(|| course::parse_date(value).ok().map(course::day))
+                    .ok_or_else(|| error("expected a date like 2024-10-01"))?;
+                match exact {
+                    true => set(&mut self.deadline, negated, Some(course::from_day(date))),
+                    false => self.predicate(negated, Predicate::Deadline(comparison, date))?,
+                }
+                Ok(())
+            }
+            "type" if exact => {
+                let presenting_type = AchievementPresention::ALL
+                    .into_iter()
+                    .find(|presenting_type| presenting_type.to_string().eq_ignore_ascii_case(value))
+                    .ok_or_else(|| error("expected a way of presenting, such as Lab"))?;
+                set(&mut self.presenting_type, negated, presenting_type);
+                Ok(())
+            }
+            "lang" if exact => {
+                let programming_language = AchievementLanguage::ALL
+                    .into_iter()
+                    .find(|language| language.to_string().eq_ignore_ascii_case(value))
+                    .or_else(|| AchievementLanguage::from_string(value))
+                    .ok_or_else(|| error("expected a language, such as C"))?;
+                set(
+                    &mut self.programming_language,
+                    negated,
+                    programming_language,
+                );
+                Ok(())
+            }
+            "sprint" if exact => {
+                let sprint = Sprint::ALL
+                    .into_iter()
+                    .find(|sprint| {
+                        let name = format!("{:?}", sprint);
+                        name.eq_ignore_ascii_case(value)
+                            || name.eq_ignore_ascii_case(&format!("Sprint{}", value))
+                    })
+                    .or_else(|| Sprint::from_string(value))
+                    .ok_or_else(|| error("expected a sprint, such as 2 or Project"))?;
+                set(&mut self.sprint, negated, sprint);
+                Ok(())
+            }
+            "id" | "title" | "comment" | "done" | "soon" | "type" | "lang" | "sprint" => {
+                Err(error("can only be compared with : or ="))
+            }
+            _ => Err(error("unknown field")),
+        }
+    }
+
+    fn predicate(&mut self, negated: bool, predicate: Predicate) -> Result<(), QueryError> {
+        self.predicates.push(match negated {
+            true => Predicate::Not(Box::new(predicate)),
+            false => predicate,
+        });
+        Ok(())
+    }
+}
+
+/// The filters written as a query that [`Filters::parse`] reads back as the same filters.
+impl std::fmt::Display for Filters {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut terms = Vec::new();
+        terms.extend(written(&self.id, |id| format!("id={}", quoted(id))));
+        terms.extend(written(&self.title, |title| {
+            format!("title={}", quoted(title))
+        }));
+        terms.extend(written(&self.deadline, |deadline| match deadline {
+            Some(deadline) => format!("deadline={}", course::day(*deadline).format("%Y-%m-%d")),
+            None => String::from("deadline=none"),
+        }));
+        terms.extend(written(&self.done, |done| format!("done={}", done)));
+        terms.extend(written(&self.present_soon, |soon| format!("soon={}", soon)));
+        terms.extend(written(&self.grade, |grade| format!("grade={}", grade)));
+        terms.extend(written(&self.presenting_type, |presenting_type| {
+            format!("type={}", quoted(&presenting_type.to_string()))
+        }));
+        terms.extend(written(&self.programming_language, |language| {
+            format!("lang={}", quoted(&language.to_string()))
+        }));
+        terms.extend(written(&self.sprint, |sprint| {
+            format!("sprint={:?}", sprint)
+        }));
+        terms.extend(written(&self.comment, |comment| match comment {
+            Some(comment) => format!("comment={}", quoted(comment)),
+            None => String::from("comment=none"),
+        }));
+        terms.extend(
+            self.predicates
+                .iter()
+                .map(|predicate| predicate.to_string()),
+        );
+        write!(f, "{}", terms.join(" "))
+    }
+}
+
+fn set<T: PartialEq>(filter: &mut Filter<T>, negated: bool, value: T) {
+    match negated {
+        true => filter.remove(value),
+        false => filter.include(value),
+    }
+}
+
+fn written<T>(filter: &Filter<T>, term: impl Fn(&T) -> String) -> Vec<String> {
+    filter
+        .value
+        .iter()
+        .map(|value| match filter.typ {
+            FilterType::Include => term(value),
+            FilterType::Remove => format!("!{}", term(value)),
+        })
+        .collect()
+}
+
+/// Splits a query at spaces that are not inside quotes. The quotes are kept.
+fn terms(query: &str) -> Result<Vec<String>, QueryError> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut quoted = false;
+    let mut chars = query.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // Inside quotes a backslash keeps the next character, `\"` being a quote.
+            '\\' if quoted => {
+                term.push(c);
+                term.extend(chars.next());
+            }
+            '"' => {
+                quoted = !quoted;
+                term.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !term.is_empty() {
+                    terms.push(std::mem::take(&mut term));
+                }
+            }
+            c => term.push(c),
+        }
+    }
+    if quoted {
+        return Err(QueryError {
+            term,
+            reason: "missing closing quote",
+        });
+    }
+    if !term.is_empty() {
+        terms.push(term);
+    }
+    Ok(terms)
+}
+
+/// Splits `grade>=4` into the field, the comparison and the value.
+/// `:` counts as [`Comparison::Equal`]. Operators inside quotes are not looked at.
+fn split(term: &str) -> Option<(&str, Comparison, &str)> {
+    let end = term.find('"').unwrap_or(term.len());
+    let start = term[..end].find([':', '<', '>', '='])?;
+    let (comparison, length) = match &term[start..] {
+        rest if rest.starts_with("<=") => (Comparison::AtMost, 2),
+        rest if rest.starts_with(">=") => (Comparison::AtLeast, 2),
+        rest if rest.starts_with('<') => (Comparison::Less, 1),
+        rest if rest.starts_with('>') => (Comparison::Greater, 1),
+        _ => (Comparison::Equal, 1),
+    };
+    Some((&term[..start], comparison, &term[start + length..]))
+}
+
+/// The text of a quoted value with its escapes undone, or the value as it is if it is not quoted.
+fn unquoted(value: &str) -> String {
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+    let mut text = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.extend(chars.next()),
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+/// `text` as a value [`unquoted`] reads back, quoting and escaping it when needed.
+fn quoted(text: &str) -> String {
+    match text.is_empty() || text.contains(|c: char| c.is_whitespace() || "\"!:<>=".contains(c)) {
+        true => quote(text),
+        false => text.to_string(),
+    }
+}
+
+/// `text` in quotes, escaping quotes and backslashes inside it.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::{PresentationType, ProgrammingLanguage};
+
+    fn achievement(id: &str, grade: i8, done: bool, comment: Option<&str>) -> Achievement {
+        Achievement {
+            id: id.to_string(),
+            title: format!("Title of {}", id),
+            deadline: Some(course::parse_date("Oct 1, 2024").unwrap()),
+            done,
+            present_soon: false,
+            grade,
+            presenting_type: AchievementPresention::Single(PresentationType::Lab),
+            programming_language: AchievementLanguage::Single(ProgrammingLanguage::C),
+            sprint: Sprint::Sprint2,
+            comment: comment.map(|comment| comment.to_string()),
+        }
+    }
+
+    #[test]
+    fn query_combines_filters_and_predicates() {
+        let filters = Filters::parse(r#"grade>=4 lang:C !done sprint:2 "pekare""#).unwrap();
+
+        assert!(filters.matches(&achievement("A1", 4, false, Some("Om Pekare"))));
+        assert!(!filters.matches(&achievement("A2", 3, false, Some("pekare"))));
+        assert!(!filters.matches(&achievement("A3", 5, true, Some("pekare"))));
+        assert!(!filters.matches(&achievement("A4", 5, false, None)));
+
+        let filters = Filters::parse(r#"deadline<2024-10-02 !deadline<="Oct 1, 2024""#).unwrap();
+        assert!(!filters.matches(&achievement("A1", 3, false, None)));
+    }
+
+    #[test]
+    fn written_query_reads_back_the_same() {
+        let filters = Filters::parse(
+            r#"id=A1 id=A2 !title="Title of A3" deadline=none !done soon=true grade=3
+            type="Lab, Studium" !lang="C & Java" sprint=Project comment=none
+            title:of !"a b" grade<5 !deadline>=2024-09-01 comment:"say \"hi\" \\o/"
+            "done" !"soon" pekare"#,
+        )
+        .unwrap();
+        assert!(filters
+            .predicates
+            .contains(&Predicate::Text(String::from("done"))));
+        assert!(filters.predicates.contains(&Predicate::Contains(
+            TextField::Comment,
+            String::from(r#"say "hi" \o/"#)
+        )));
+
+        let query = filters.to_string();
+        assert_eq!(Filters::parse(&query).unwrap(), filters, "{}", query);
+    }
+
+    #[test]
+    fn unknown_fields_and_values_are_reported() {
+        assert_eq!(
+            Filters::parse("colour:red").unwrap_err().reason,
+            "unknown field"
+        );
+        assert_eq!(Filters::parse("lang:Rust").unwrap_err().term, "lang:Rust");
+        assert_eq!(
+            Filters::parse("grade>=high").unwrap_err().term,
+            "grade>=high"
+        );
+        assert_eq!(
+            Filters::parse("\"pekare").unwrap_err().reason,
+            "missing closing quote"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::application::Application;
+use achievements_enhanced::achievement_csv::{ACHIEVEMENTS_FILE, COLUMNS};
+use eframe::egui;
+
+/// What the user picked to do with the broken rows this frame.
+enum Recovery {
+    Fix(usize),
+    Skip(usize),
+    SkipAll,
+    Default(usize),
+    ReadAgain,
+    ResetToDefaults,
+}
+
+impl Application {
+    /// Lists the rows of achievements.csv that could not be parsed
+    /// and lets the user fix, skip or replace each of them.
+    /// If the file could not be read at all, shows why instead.
+    pub fn recovery_ui(&mut self, ctx: &egui::Context) {
+        if self.workspace().broken_rows.is_empty() && self.workspace().load_error.is_none() {
+            return;
+        }
+
+        let language = &self.language;
+        let workspace = &mut self.workspaces[self.active_workspace];
+        let mut recovery = None;
+
+        egui::Window::new(language.broken_rows.clone())
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if let Some(error) = &workspace.load_error {
+                    ui.label(format!(
+                        "{} {}: {}",
+                        language.unreadable_file,
+                        workspace.path.join(ACHIEVEMENTS_FILE).display(),
+                        error
+                    ));
+                    ui.label(language.unreadable_file_description.clone());
+                    ui.allocate_space(egui::vec2(0.0, 10.0));
+                    ui.horizontal(|ui| {
+                        if ui.button(language.read_again.clone()).clicked() {
+                            recovery = Some(Recovery::ReadAgain);
+                        }
+                        if ui.button(language.reset_to_defaults.clone()).clicked() {
+                            recovery = Some(Recovery::ResetToDefaults);
+                        }
+                    });
+                    return;
+                }
+
+                ui.label(format!(
+                    "{}: {}",
+                    language.broken_rows_description,
+                    workspace.path.join(ACHIEVEMENTS_FILE).display()
+                ));
+                ui.allocate_space(egui::vec2(0.0, 10.0));
+
+                egui::ScrollArea::vertical()
+                    .max_height(self.settings.font_size * 30.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("Broken Rows Grid")
+                            .striped(true)
+                            .spacing(egui::vec2(
+                                self.settings.font_size,
+                                self.settings.font_size / 2.0,
+                            ))
+                            .show(ui, |ui| {
+                                for i in 0..workspace.broken_rows.len() {
+                                    let has_default = workspace.default_for_broken_row(i).is_some();
+                                    let broken_row = &mut workspace.broken_rows[i];
+
+                                    ui.label(format!("{} {}", language.row, broken_row.row));
+                                    ui.label(
+                                        broken_row.record.first().cloned().unwrap_or_default(),
+                                    );
+                                    ui.vertical(|ui| {
+                                        for error in broken_row.errors.clone() {
+                                            let Some(column) =
+                                                COLUMNS.iter().position(|c| *c == error.column)
+                                            else {
+                                                continue;
+                                            };
+                                            if broken_row.record.len() <= column {
+                                                broken_row
+                                                    .record
+                                                    .resize(COLUMNS.len(), String::new());
+                                            }
+                                            ui.horizontal(|ui| {
+                                                ui.label(error.column);
+                                                ui.text_edit_singleline(
+                                                    &mut broken_row.record[column],
+                                                )
+                                                .on_hover_text(format!(
+                                                    "{} \"{}\"",
+                                                    language.invalid_value, error.value
+                                                ));
+                                            });
+                                        }
+                                    });
+                                    if ui.button(language.fix.clone()).clicked() {
+                                        recovery = Some(Recovery::Fix(i));
+                                    }
+                                    if ui.button(language.skip.clone()).clicked() {
+                                        recovery = Some(Recovery::Skip(i));
+                                    }
+                                    if ui
+                                        .add_enabled(
+                                            has_default,
+                                            egui::Button::new(language.use_default.clone()),
+                                        )
+                                        .clicked()
+                                    {
+                                        recovery = Some(Recovery::Default(i));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.allocate_space(egui::vec2(0.0, 10.0));
+                ui.horizontal(|ui| {
+                    if ui.button(language.skip_all.clone()).clicked() {
+                        recovery = Some(Recovery::SkipAll);
+                    }
+                    if ui.button(language.reset_to_defaults.clone()).clicked() {
+                        recovery = Some(Recovery::ResetToDefaults);
+                    }
+                });
+            });
+
+        if let Some(recovery) = recovery {
+            self.change_workspace(|workspace| {
+                match recovery {
+                    Recovery::Fix(i) => workspace.fix_broken_row(i),
+                    Recovery::Skip(i) => workspace.skip_broken_row(i),
+                    Recovery::SkipAll => workspace.skip_all_broken_rows(),
+                    Recovery::Default(i) => workspace.default_broken_row(i),
+                    Recovery::ReadAgain => workspace.reload(&workspace.achievements.clone()),
+                    Recovery::ResetToDefaults => workspace.reset_to_defaults(),
+                }
+                true
+            });
+        }
+    }
+}
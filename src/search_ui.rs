@@ -0,0 +1,47 @@
+use crate::application::{Application, Filters};
+use eframe::egui;
+
+impl Application {
+    /// Search bar above the achievements grid, showing the filters in use as a query.
+    pub fn search_ui(&mut self, ui: &mut egui::Ui) {
+        let id = ui.make_persistent_id("Search");
+
+        // Unless the query is being edited it follows the filters, including those picked by right-click.
+        if !ui.memory(|memory| memory.has_focus(id)) {
+            self.search = self.filters.to_string();
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(self.language.search.clone())
+                .on_hover_text(self.language.search_help.clone());
+            let search = ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .id(id)
+                    .hint_text(r#"grade>=4 lang:C !done sprint:2 "pekare""#)
+                    .desired_width(self.settings.font_size * 40.0),
+            );
+            if search.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                match Filters::parse(&self.search) {
+                    Ok(filters) => {
+                        self.filters = filters;
+                        self.search_error = None;
+                    }
+                    Err(e) => {
+                        self.search_error = Some(e.to_string());
+                        search.request_focus();
+                    }
+                }
+            }
+            if search.changed() {
+                self.search_error = None;
+            }
+            if ui.button(self.language.clear_filters.clone()).clicked() {
+                self.filters = Filters::new();
+                self.search_error = None;
+            }
+            if let Some(error) = &self.search_error {
+                ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+        });
+    }
+}
@@ -1,8 +1,10 @@
 use crate::application::Application;
-use crate::burndown;
 use crate::langs::{get_english, get_swedish, Langs};
+use achievements_enhanced::burndown;
 use eframe::egui;
 
+use std::path::PathBuf;
+
 impl Application {
     pub fn settings_ui(&mut self, ctx: &egui::Context) {
         let mut font_size = self.settings.font_size;
@@ -30,23 +32,16 @@ impl Application {
                     ui.toggle_value(&mut self.settings.dark_mode, dark_mode.to_string());
                     ui.end_row();
                     self.heading(ui, self.language.target_grade.clone());
-                    let slider = ui.add(egui::Slider::new(
-                        &mut self.progress_tracker.target_grade,
-                        3..=5,
-                    ));
+                    let grades = self.workspace().course.grade_range();
+                    let slider = ui.add(egui::Slider::new(&mut self.settings.target_grade, grades));
                     if slider.drag_stopped() {
-                        self.progress_tracker.update();
-                        self.settings.save();
+                        self.configure_progress();
                     }
                     ui.end_row();
                     self.heading(ui, self.language.max_per_lab.clone());
-                    let slider = ui.add(egui::Slider::new(
-                        &mut self.progress_tracker.max_per_lab,
-                        1..=4,
-                    ));
+                    let slider = ui.add(egui::Slider::new(&mut self.settings.max_per_lab, 1..=4));
                     if slider.drag_stopped() {
-                        self.progress_tracker.update();
-                        self.settings.save();
+                        self.configure_progress();
                     }
                     ui.end_row();
                     self.heading(ui, self.language.language.clone());
@@ -70,9 +65,58 @@ impl Application {
                     }
                     ui.end_row();
 
+                    self.heading(ui, self.language.edit_commit_message.clone());
+                    if ui
+                        .button(self.settings.edit_commit_message.to_string())
+                        .clicked()
+                    {
+                        self.settings.edit_commit_message = !self.settings.edit_commit_message;
+                        self.settings.save();
+                    }
+                    ui.end_row();
+
+                    self.heading(ui, self.language.data_dir.clone());
+                    ui.label(self.settings.data_dir.display().to_string())
+                        .on_hover_text(self.language.data_dir_hint.clone());
+                    ui.end_row();
+
+                    self.heading(ui, self.language.courses.clone());
+                    ui.vertical(|ui| {
+                        let mut remove = None;
+                        for (i, workspace) in self.workspaces.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!(
+                                    "{} ({})",
+                                    workspace.course.name,
+                                    workspace.path.display()
+                                ));
+                                if self.workspaces.len() > 1
+                                    && ui.button(self.language.remove.clone()).clicked()
+                                {
+                                    remove = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove {
+                            self.remove_workspace(i);
+                        }
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut self.new_course);
+                            if ui.button(self.language.add_course.clone()).clicked()
+                                && !self.new_course.trim().is_empty()
+                            {
+                                self.add_workspace(PathBuf::from(self.new_course.trim()));
+                                self.new_course.clear();
+                            }
+                        });
+                    });
+                    ui.end_row();
+
                     self.heading(ui, self.language.burndown.clone());
                     if ui.button(self.language.generate.to_string()).clicked() {
-                        burndown::generate();
+                        if let Err(e) = burndown::generate(&self.workspace().path) {
+                            eprintln!("Error generating burndown: {}", e);
+                        }
                     }
                 });
         });
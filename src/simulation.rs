@@ -0,0 +1,109 @@
+use crate::achievements::Achievement;
+use crate::course::Course;
+use crate::progress_tracker::{Change, ProgressTracker, ProgressTrackerMode};
+use crate::store::AchievementStore;
+
+use chrono::{DateTime, Local};
+
+/// A copy of a course's achievements and progress tracker to try things on,
+/// such as skipping a lab or presenting more per lab, without saving anything.
+pub struct Simulation {
+    pub course: Course,
+    pub achievements: Vec<Achievement>,
+    pub progress_tracker: ProgressTracker,
+}
+
+impl Simulation {
+    pub fn new(store: &AchievementStore) -> Self {
+        Self {
+            course: store.course.clone(),
+            achievements: store.achievements.clone(),
+            progress_tracker: store.progress_tracker.clone(),
+        }
+    }
+
+    pub fn set_done(&mut self, id: &str, done: bool) {
+        let Some(achievement) = self
+            .achievements
+            .iter_mut()
+            .find(|achievement| achievement.id.eq_ignore_ascii_case(id))
+        else {
+            return;
+        };
+        let before = achievement.clone();
+        achievement.done = done;
+        let after = achievement.clone();
+        self.progress_tracker.changed(
+            Change::Achievement {
+                before: &before,
+                after: &after,
+            },
+            &self.course,
+            &self.achievements,
+        );
+    }
+
+    /// Cancels the lab at `date`, or brings it back if it was cancelled.
+    pub fn toggle_lab(&mut self, date: DateTime<Local>) {
+        for lab in self.course.labs.iter_mut().filter(|lab| lab.date == date) {
+            lab.cancelled = !lab.cancelled;
+        }
+        self.changed(Change::Calendar);
+    }
+
+    pub fn set_max_per_lab(&mut self, max_per_lab: u8) {
+        self.progress_tracker.max_per_lab = max_per_lab;
+        self.changed(Change::Parameters);
+    }
+
+    pub fn set_target_grade(&mut self, target_grade: i8) {
+        self.progress_tracker.target_grade = target_grade;
+        self.changed(Change::Parameters);
+    }
+
+    pub fn set_mode(&mut self, mode: ProgressTrackerMode) {
+        self.progress_tracker.mode = mode;
+        self.changed(Change::Parameters);
+    }
+
+    fn changed(&mut self, change: Change) {
+        self.progress_tracker
+            .changed(change, &self.course, &self.achievements);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievement_csv::ACHIEVEMENTS_FILE;
+    use crate::achievements::PresentationType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn simulating_leaves_the_store_alone() {
+        let dir = TempDir::new().unwrap();
+        let store = AchievementStore::open(dir.path());
+        let mut simulation = Simulation::new(&store);
+        let lab = store.course.labs[0].date;
+        let id = store.achievements[0].id.clone();
+
+        simulation.set_done(&id, true);
+        simulation.toggle_lab(lab);
+        simulation.set_max_per_lab(1);
+
+        assert!(simulation.achievements[0].done);
+        assert!(simulation.course.labs[0].cancelled);
+        assert!(!simulation
+            .progress_tracker
+            .days
+            .iter()
+            .any(|day| day.date == lab && day.presentations.contains(&PresentationType::Lab)));
+        assert!(!store.achievements[0].done);
+        assert!(!store.course.labs[0].cancelled);
+        assert_eq!(store.progress_tracker.max_per_lab, 4);
+        assert!(!dir.path().join(ACHIEVEMENTS_FILE).exists());
+
+        simulation.toggle_lab(lab);
+        assert_eq!(simulation.course.labs, store.course.labs);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::achievements::Achievement;
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Direction::Ascending => write!(f, "ascending"),
+            Direction::Descending => write!(f, "descending"),
+        }
+    }
+}
+
+impl Direction {
+    pub fn reverse(&self) -> Direction {
+        match self {
+            Direction::Ascending => Direction::Descending,
+            Direction::Descending => Direction::Ascending,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Fieled {
+    ID,
+    Title,
+    Deadline,
+    Done,
+    PresentSoon,
+    Grade,
+    PresentingType,
+    ProgrammingLanguage,
+    Sprint,
+    Comment,
+}
+
+impl Fieled {
+    /// Compares two achievements by this field, in ascending order.
+    /// Ids are compared naturally, so that A2 comes before A10.
+    pub fn compare(&self, a: &Achievement, b: &Achievement) -> Ordering {
+        match self {
+            Fieled::ID => natural_cmp(&a.id, &b.id),
+            Fieled::Title => a.title.cmp(&b.title),
+            Fieled::Deadline => a.deadline.cmp(&b.deadline),
+            Fieled::Done => a.done.cmp(&b.done),
+            Fieled::PresentSoon => a.present_soon.cmp(&b.present_soon),
+            Fieled::Grade => a.grade.cmp(&b.grade),
+            Fieled::PresentingType => a.presenting_type.cmp(&b.presenting_type),
+            Fieled::ProgrammingLanguage => a.programming_language.cmp(&b.programming_language),
+            Fieled::Sprint => a.sprint.cmp(&b.sprint),
+            Fieled::Comment => a.comment.cmp(&b.comment),
+        }
+    }
+}
+
+/// The fields to sort by, most important first. Achievements that are equal
+/// in every field keep their order, so an empty sort leaves the order as it is.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sort {
+    pub keys: Vec<(Fieled, Direction)>,
+}
+
+impl Sort {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The direction clicking the heading of `fieled` sorts it in.
+    /// With `add` the field is added after the others instead of replacing them.
+    pub fn next(&self, fieled: Fieled, add: bool) -> Direction {
+        let current = match add {
+            true => self.keys.iter().find(|(key, _)| *key == fieled),
+            false => self.keys.first().filter(|(key, _)| *key == fieled),
+        };
+        match current {
+            Some((_, direction)) => direction.reverse(),
+            None => Direction::Ascending,
+        }
+    }
+
+    /// Sorts by `fieled` alone, or with `add` by `fieled` after the fields sorted by already.
+    /// Clicking the same field again reverses it.
+    pub fn click(&mut self, fieled: Fieled, add: bool) {
+        let direction = self.next(fieled, add);
+        match add {
+            true => match self.keys.iter_mut().find(|(key, _)| *key == fieled) {
+                Some(key) => key.1 = direction,
+                None => self.keys.push((fieled, direction)),
+            },
+            false => self.keys = vec![(fieled, direction)],
+        }
+    }
+
+    /// Where among the fields sorted by `fieled` is, and in which direction.
+    pub fn position(&self, fieled: Fieled) -> Option<(usize, Direction)> {
+        self.keys
+            .iter()
+            .position(|(key, _)| *key == fieled)
+            .map(|i| (i, self.keys[i].1))
+    }
+
+    pub fn compare(&self, a: &Achievement, b: &Achievement) -> Ordering {
+        self.keys
+            .iter()
+            .map(|(fieled, direction)| match direction {
+                Direction::Ascending => fieled.compare(a, b),
+                Direction::Descending => fieled.compare(b, a),
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    pub fn sort(&self, achievements: &mut [Achievement]) {
+        achievements.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+/// Compares text with the numbers in it compared by value, so that `A2` comes before `A10`.
+/// Numbers written with leading zeros come first among equal values.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chunks = chunks(a);
+    let mut b_chunks = chunks(b);
+    loop {
+        let ordering = match (a_chunks.next(), b_chunks.next()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (
+                a.starts_with(|c: char| c.is_ascii_digit()),
+                b.starts_with(|c: char| c.is_ascii_digit()),
+            ) {
+                (true, true) => {
+                    let a = a.trim_start_matches('0');
+                    let b = b.trim_start_matches('0');
+                    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+                }
+                _ => a.cmp(b),
+            },
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+/// Splits text into runs of digits and runs of everything else.
+fn chunks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let digits = rest.starts_with(|c: char| c.is_ascii_digit());
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != digits)
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return None;
+        }
+        let (chunk, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some(chunk)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::{
+        AchievementLanguage, AchievementPresention, PresentationType, ProgrammingLanguage, Sprint,
+    };
+
+    fn achievement(id: &str, grade: i8, sprint: Sprint) -> Achievement {
+        Achievement {
+            id: id.to_string(),
+            title: String::new(),
+            deadline: None,
+            done: false,
+            present_soon: false,
+            grade,
+            presenting_type: AchievementPresention::Single(PresentationType::Lab),
+            programming_language: AchievementLanguage::Single(ProgrammingLanguage::C),
+            sprint,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn ids_are_ordered_naturally() {
+        let mut ids = ["A10", "Z91", "A2", "Y60", "A02", "B1", "A"];
+        ids.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(ids, ["A", "A02", "A2", "A10", "B1", "Y60", "Z91"]);
+    }
+
+    #[test]
+    fn later_keys_break_ties_and_equals_keep_their_order() {
+        let mut achievements = vec![
+            achievement("A10", 3, Sprint::Sprint2),
+            achievement("A2", 4, Sprint::Sprint2),
+            achievement("B1", 3, Sprint::Sprint1),
+            achievement("C7", 4, Sprint::Sprint2),
+            achievement("A1", 4, Sprint::Sprint2),
+        ];
+        let mut sort = Sort::new();
+        sort.click(Fieled::Sprint, false);
+        sort.click(Fieled::Grade, true);
+        sort.click(Fieled::Grade, true);
+        assert_eq!(
+            sort.keys,
+            [
+                (Fieled::Sprint, Direction::Ascending),
+                (Fieled::Grade, Direction::Descending)
+            ]
+        );
+
+        sort.sort(&mut achievements);
+        let ids = achievements
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, ["B1", "A2", "C7", "A1", "A10"]);
+
+        sort.click(Fieled::ID, true);
+        sort.sort(&mut achievements);
+        let ids = achievements
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, ["B1", "A1", "A2", "C7", "A10"]);
+
+        sort.click(Fieled::Sprint, false);
+        assert_eq!(sort.keys, [(Fieled::Sprint, Direction::Descending)]);
+    }
+}
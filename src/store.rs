@@ -0,0 +1,500 @@
+use crate::achievement_csv::{self, BrokenRow};
+use crate::achievements::{Achievement, SerializableAchievement};
+use crate::course::{self, Course, CourseError, Lab};
+use crate::history::{Command, History, Snapshot};
+use crate::progress_tracker::{Change, ProgressTracker, ProgressTrackerMode};
+
+use chrono::{DateTime, Local};
+
+use std::path::{Path, PathBuf};
+
+/// One course being tracked: its definition, the achievements file next to it
+/// and the progress tracker computed from them.
+///
+/// Each store lives in its own directory holding `course.toml`,
+/// `achievements.csv`, `burndown.csv` and, once the labs are edited or achievements
+/// pinned, `calendar.toml` and `pins.toml`.
+///
+/// The store is the only owner of the course and achievements. The fields are public
+/// for code that needs to borrow them separately, but changing them through the methods
+/// reports each change to the progress tracker so it stays up to date.
+pub struct AchievementStore {
+    pub path: PathBuf,
+    pub course: Course,
+    pub achievements: Vec<Achievement>,
+    /// Rows of achievements.csv that could not be parsed and still wait for the user.
+    pub broken_rows: Vec<BrokenRow>,
+    /// Why achievements.csv could not be read at all. While this is set,
+    /// saving leaves the file alone so nothing the app did not understand is overwritten.
+    pub load_error: Option<String>,
+    pub progress_tracker: ProgressTracker,
+    /// Every change made to the achievements through the methods, for undo and redo.
+    pub history: History,
+}
+
+impl AchievementStore {
+    /// Loads the course and achievements in the directory `path`.
+    ///
+    /// A missing `course.toml` falls back to the bundled course and a missing
+    /// `achievements.csv` starts from the course's achievements, so this never fails.
+    /// Rows that cannot be parsed end up in [`AchievementStore::broken_rows`],
+    /// any other error reading the file in [`AchievementStore::load_error`].
+    pub fn open(path: &Path) -> Self {
+        let course = Course::load(path);
+
+        let mut load_error = None;
+        let (achievements, broken_rows) = match achievement_csv::read_achievements(path) {
+            Ok(file) => (file.achievements, file.broken_rows),
+            Err(e) if is_not_found(&e) => (achievement_csv::read_defaults(&course), Vec::new()),
+            Err(e) => {
+                load_error = Some(e.to_string());
+                (Vec::new(), Vec::new())
+            }
+        };
+
+        let mut progress_tracker =
+            ProgressTracker::new(4, 5, ProgressTrackerMode::Left, &course, &achievements);
+        progress_tracker.load_pins(path);
+        progress_tracker.update(&course, &achievements);
+
+        Self {
+            path: path.to_path_buf(),
+            course,
+            achievements,
+            broken_rows,
+            load_error,
+            progress_tracker,
+            history: History::default(),
+        }
+    }
+
+    /// Writes achievements.csv. Rows that are still broken are written back
+    /// unchanged so that saving never throws away data the user has not decided on.
+    /// Does nothing while [`AchievementStore::load_error`] is set.
+    pub fn save_achievements(&self) -> Result<(), csv::Error> {
+        if self.load_error.is_some() {
+            return Ok(());
+        }
+        let mut wtr = csv::WriterBuilder::new()
+            .flexible(true)
+            .from_path(self.path.join(achievement_csv::ACHIEVEMENTS_FILE))?;
+        if self.achievements.is_empty() {
+            wtr.write_record(achievement_csv::COLUMNS)?;
+        }
+        for achievement in self.achievements.clone() {
+            let serializable_achievement = SerializableAchievement {
+                id: achievement.id.clone(),
+                link: self.course.link(&achievement.id),
+                title: achievement.title.clone(),
+                deadline: achievement.deadline.map(course::format_date),
+                done: achievement.done,
+                present_soon: achievement.present_soon,
+                grade: achievement.grade,
+                presenting_type: achievement.presenting_type.to_string(),
+                programming_language: achievement.programming_language.to_string(),
+                sprint: achievement.sprint.clone(),
+                comment: achievement.comment.clone(),
+            };
+            wtr.serialize(serializable_achievement)?;
+        }
+        for broken_row in self.broken_rows.iter() {
+            wtr.write_record(&broken_row.record)?;
+        }
+        Ok(())
+    }
+
+    /// The achievement with the given id, ignoring case.
+    pub fn get(&self, id: &str) -> Option<&Achievement> {
+        self.achievements
+            .iter()
+            .find(|achievement| achievement.id.eq_ignore_ascii_case(id))
+    }
+
+    /// The achievements matching `predicate`, in file order.
+    pub fn query<'a>(
+        &'a self,
+        predicate: impl Fn(&Achievement) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a Achievement> + 'a {
+        self.achievements
+            .iter()
+            .filter(move |achievement| predicate(achievement))
+    }
+
+    /// Changes the achievement with the given id and updates the progress tracker.
+    /// Returns `false` if there is no such achievement.
+    pub fn edit(&mut self, id: &str, change: impl FnOnce(&mut Achievement)) -> bool {
+        let Some(achievement) = self
+            .achievements
+            .iter_mut()
+            .find(|achievement| achievement.id.eq_ignore_ascii_case(id))
+        else {
+            return false;
+        };
+        let before = achievement.clone();
+        change(achievement);
+        let after = achievement.clone();
+        self.changed(Change::Achievement {
+            before: &before,
+            after: &after,
+        });
+        if before != after {
+            self.history.record(Command::Edit(vec![(before, after)]));
+        }
+        true
+    }
+
+    /// Changes every achievement, e.g. to clear all done marks.
+    /// Returns `false` if none of them changed.
+    pub fn edit_all(&mut self, change: impl Fn(&mut Achievement)) -> bool {
+        let before = self.achievements.clone();
+        self.achievements.iter_mut().for_each(change);
+        self.update_progress();
+        let changes = before
+            .into_iter()
+            .zip(self.achievements.iter().cloned())
+            .filter(|(before, after)| before != after)
+            .collect::<Vec<(Achievement, Achievement)>>();
+        if changes.is_empty() {
+            return false;
+        }
+        self.history.record(Command::Edit(changes));
+        true
+    }
+
+    pub fn set_done(&mut self, id: &str, done: bool) -> bool {
+        self.edit(id, |achievement| achievement.done = done)
+    }
+
+    pub fn set_present_soon(&mut self, id: &str, present_soon: bool) -> bool {
+        self.edit(id, |achievement| achievement.present_soon = present_soon)
+    }
+
+    /// Replaces every achievement, e.g. with the result of a merge.
+    pub fn set_achievements(&mut self, achievements: Vec<Achievement>) {
+        let before = self.snapshot();
+        self.achievements = achievements;
+        self.update_progress();
+        self.replaced(before);
+    }
+
+    /// Takes back the last change to the achievements. Returns `false` if there is none.
+    pub fn undo(&mut self) -> bool {
+        match self.history.undo() {
+            Some(command) => {
+                self.apply(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Makes the last undone change again. Returns `false` if there is none.
+    pub fn redo(&mut self) -> bool {
+        match self.history.redo() {
+            Some(command) => {
+                self.apply(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn apply(&mut self, command: Command) {
+        match command {
+            Command::Edit(changes) => {
+                for (_, after) in changes {
+                    if let Some(achievement) = self
+                        .achievements
+                        .iter_mut()
+                        .find(|achievement| achievement.id == after.id)
+                    {
+                        *achievement = after;
+                    }
+                }
+            }
+            Command::Replace { after, .. } => {
+                self.achievements = self.in_current_order(after.achievements);
+                self.broken_rows = after.broken_rows;
+            }
+        }
+        self.update_progress();
+    }
+
+    /// `achievements` in the order the achievements are in now, which may have been
+    /// sorted since they were recorded. Achievements that are not there now go last.
+    fn in_current_order(&self, mut achievements: Vec<Achievement>) -> Vec<Achievement> {
+        achievements.sort_by_key(|achievement| {
+            self.achievements
+                .iter()
+                .position(|current| current.id == achievement.id)
+                .unwrap_or(usize::MAX)
+        });
+        achievements
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            achievements: self.achievements.clone(),
+            broken_rows: self.broken_rows.clone(),
+        }
+    }
+
+    /// Records everything that changed since `before` was taken as one command.
+    fn replaced(&mut self, before: Snapshot) {
+        let after = self.snapshot();
+        if before != after {
+            self.history.record(Command::Replace { before, after });
+        }
+    }
+
+    /// Recomputes the progress tracker after the achievements were changed directly.
+    pub fn update_progress(&mut self) {
+        self.changed(Change::Achievements);
+    }
+
+    fn changed(&mut self, change: Change) {
+        self.progress_tracker
+            .changed(change, &self.course, &self.achievements);
+    }
+
+    /// Changes what the progress tracker aims for and how it counts.
+    pub fn configure_progress(
+        &mut self,
+        max_per_lab: u8,
+        target_grade: i8,
+        mode: ProgressTrackerMode,
+    ) {
+        self.progress_tracker.max_per_lab = max_per_lab;
+        self.progress_tracker.target_grade = target_grade;
+        self.progress_tracker.mode = mode;
+        self.changed(Change::Parameters);
+    }
+
+    /// Changes the lab calendar, saves it to `calendar.toml` and updates the progress tracker.
+    /// The labs are kept in date order.
+    ///
+    /// Pins on a lab that moved follow it, and pins left on a day without a session
+    /// that can take them go to the next one that can.
+    pub fn edit_calendar(&mut self, change: impl FnOnce(&mut Vec<Lab>)) -> Result<(), CourseError> {
+        let before = self.course.labs.clone();
+        change(&mut self.course.labs);
+        // Moving, labelling or cancelling a lab keeps it in its place in the list.
+        if before.len() == self.course.labs.len() {
+            for (old, new) in before.iter().zip(self.course.labs.iter()) {
+                if old.date != new.date {
+                    self.progress_tracker.move_pins(old.date, new.date);
+                }
+            }
+        }
+        self.course.labs.sort_by_key(|lab| lab.date);
+        self.progress_tracker
+            .remap_pins(&self.course, &self.achievements);
+        self.changed(Change::Calendar);
+        self.course.save_calendar(&self.path)?;
+        self.progress_tracker.save_pins(&self.path)
+    }
+
+    /// Achievements that could be pinned to the day at `date`.
+    pub fn pin_candidates(&self, date: DateTime<Local>) -> Vec<&Achievement> {
+        self.progress_tracker
+            .candidates(&self.course, &self.achievements, date)
+    }
+
+    /// Keeps the achievement with the given id on the day at `date` in the suggested plan
+    /// and saves the pins to `pins.toml`.
+    pub fn pin(&mut self, id: &str, date: DateTime<Local>) -> Result<(), CourseError> {
+        self.progress_tracker.pin(id, date);
+        self.update_progress();
+        self.progress_tracker.save_pins(&self.path)
+    }
+
+    pub fn unpin(&mut self, id: &str) -> Result<(), CourseError> {
+        self.progress_tracker.unpin(id);
+        self.update_progress();
+        self.progress_tracker.save_pins(&self.path)
+    }
+
+    /// Reads achievements.csv again after git changed it.
+    /// Achievements edited since `snapshot` was taken keep their local version.
+    ///
+    /// The history is cleared, undoing past the reload would bring back what git replaced.
+    /// If the file cannot be read, the achievements stay as they are and the error ends up in
+    /// [`AchievementStore::load_error`].
+    pub fn reload(&mut self, snapshot: &[Achievement]) {
+        let file = match achievement_csv::read_achievements(&self.path) {
+            Ok(file) => file,
+            Err(e) => {
+                self.load_error = Some(e.to_string());
+                return;
+            }
+        };
+        self.load_error = None;
+        let edited = self
+            .achievements
+            .iter()
+            .filter(|achievement| !snapshot.contains(achievement))
+            .cloned()
+            .collect::<Vec<Achievement>>();
+
+        self.achievements = file
+            .achievements
+            .into_iter()
+            .map(|achievement| {
+                edited
+                    .iter()
+                    .find(|edited| edited.id == achievement.id)
+                    .cloned()
+                    .unwrap_or(achievement)
+            })
+            .collect();
+        self.broken_rows = file.broken_rows;
+        self.update_progress();
+        self.history.clear();
+    }
+
+    /// Parses a broken row again after the user edited it.
+    pub fn fix_broken_row(&mut self, index: usize) {
+        let before = self.snapshot();
+        let broken_row = &mut self.broken_rows[index];
+        match achievement_csv::parse_record(broken_row.row, &broken_row.record) {
+            Ok(achievement) => {
+                let position = broken_row.position.min(self.achievements.len());
+                self.achievements.insert(position, achievement);
+                self.broken_rows.remove(index);
+                self.update_progress();
+                self.replaced(before);
+            }
+            Err(errors) => broken_row.errors = errors,
+        }
+    }
+
+    pub fn skip_broken_row(&mut self, index: usize) {
+        let before = self.snapshot();
+        self.broken_rows.remove(index);
+        self.replaced(before);
+    }
+
+    /// Drops every broken row, they are not written back to achievements.csv anymore.
+    pub fn skip_all_broken_rows(&mut self) {
+        let before = self.snapshot();
+        self.broken_rows.clear();
+        self.replaced(before);
+    }
+
+    /// The course's own version of the achievement in a broken row, if the course defines it.
+    pub fn default_for_broken_row(&self, index: usize) -> Option<&Achievement> {
+        let id = self.broken_rows[index].record.first()?;
+        self.course
+            .achievements
+            .iter()
+            .find(|achievement| &achievement.id == id)
+    }
+
+    pub fn default_broken_row(&mut self, index: usize) {
+        if let Some(achievement) = self.default_for_broken_row(index).cloned() {
+            let before = self.snapshot();
+            let position = self.broken_rows[index]
+                .position
+                .min(self.achievements.len());
+            self.achievements.insert(position, achievement);
+            self.broken_rows.remove(index);
+            self.update_progress();
+            self.replaced(before);
+        }
+    }
+
+    /// Throws away achievements.csv as read and starts over from the course definition.
+    pub fn reset_to_defaults(&mut self) {
+        let before = self.snapshot();
+        self.achievements = achievement_csv::read_defaults(&self.course);
+        self.broken_rows.clear();
+        self.load_error = None;
+        self.update_progress();
+        self.replaced(before);
+    }
+}
+
+fn is_not_found(e: &csv::Error) -> bool {
+    matches!(e.kind(), csv::ErrorKind::Io(e) if e.kind() == std::io::ErrorKind::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    #[test]
+    fn deadlines_read_back_as_the_same_day() {
+        let dir = TempDir::new().unwrap();
+        let mut store = AchievementStore::open(dir.path());
+        let id = store.achievements[0].id.clone();
+        let day = NaiveDate::from_ymd_opt(2024, 9, 5).unwrap();
+        store.edit(&id, |achievement| {
+            achievement.deadline = Some(course::from_day(day))
+        });
+        store.save_achievements().unwrap();
+
+        let contents =
+            std::fs::read_to_string(dir.path().join(achievement_csv::ACHIEVEMENTS_FILE)).unwrap();
+        assert!(contents.contains("Sep 5, 2024"));
+        let store = AchievementStore::open(dir.path());
+        let deadline = store.get(&id).unwrap().deadline.unwrap();
+        assert_eq!(course::day(deadline), day);
+    }
+
+    #[test]
+    fn pins_are_saved_and_follow_the_calendar() {
+        let dir = TempDir::new().unwrap();
+        let mut store = AchievementStore::open(dir.path());
+        let index = store.course.labs.len() / 2;
+        let lab = store.course.labs[index].date;
+        let id = store.pin_candidates(lab)[0].id.clone();
+        store.pin(&id, lab).unwrap();
+        assert!(AchievementStore::open(dir.path())
+            .progress_tracker
+            .is_pinned(&id, lab));
+
+        let moved = lab + chrono::Duration::days(1);
+        store
+            .edit_calendar(|labs| labs[index].date = moved)
+            .unwrap();
+        assert!(store.progress_tracker.is_pinned(&id, moved));
+        assert!(AchievementStore::open(dir.path())
+            .progress_tracker
+            .is_pinned(&id, moved));
+
+        store
+            .edit_calendar(|labs| labs.retain(|lab| lab.date != moved))
+            .unwrap();
+        let pins = &AchievementStore::open(dir.path()).progress_tracker.pins;
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].id, id);
+        assert!(pins[0].date > moved);
+        assert!(store
+            .pin_candidates(pins[0].date)
+            .iter()
+            .any(|achievement| achievement.id == id));
+    }
+
+    #[test]
+    fn unreadable_files_are_reported_and_left_alone() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join(achievement_csv::ACHIEVEMENTS_FILE);
+        let contents = b"id,title\nA1,\xff\xfe\n";
+        std::fs::write(&file, contents).unwrap();
+
+        let mut store = AchievementStore::open(dir.path());
+        assert!(store.load_error.is_some());
+        assert!(store.achievements.is_empty());
+        store.save_achievements().unwrap();
+        assert_eq!(std::fs::read(&file).unwrap(), contents);
+
+        store.reload(&[]);
+        assert!(store.load_error.is_some());
+        store.reset_to_defaults();
+        assert!(store.load_error.is_none());
+        store.save_achievements().unwrap();
+        assert!(AchievementStore::open(dir.path()).load_error.is_none());
+    }
+}
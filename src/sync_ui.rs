@@ -0,0 +1,114 @@
+use crate::application::Application;
+use crate::git_worker::GitJob;
+use eframe::egui;
+
+impl Application {
+    /// Spinner, last sync time or error, shown in the menu bar when git is used.
+    pub fn sync_status_ui(&mut self, ui: &mut egui::Ui) {
+        if !self.settings.git {
+            return;
+        }
+
+        if self.sync_status.running.is_some() {
+            ui.spinner();
+            ui.label(self.language.syncing.clone());
+        } else if let Some(error) = &self.sync_status.error {
+            let status = ui
+                .label(
+                    egui::RichText::new(self.language.sync_failed.clone())
+                        .color(ui.visuals().error_fg_color),
+                )
+                .on_hover_text(error.clone())
+                .on_hover_cursor(egui::CursorIcon::PointingHand);
+            if status.clicked() {
+                self.start_git(GitJob::Sync);
+            }
+        } else if let Some(last_sync) = self.sync_status.last_sync {
+            let status = ui
+                .label(format!(
+                    "{} {}",
+                    self.language.last_sync,
+                    last_sync.format("%H:%M")
+                ))
+                .on_hover_text(self.language.sync.clone())
+                .on_hover_cursor(egui::CursorIcon::PointingHand);
+            if status.clicked() {
+                self.start_git(GitJob::Sync);
+            }
+        }
+    }
+
+    /// Shown while the final sync runs after the user asked to close the window,
+    /// and before that while the user edits the commit message if they chose to.
+    pub fn closing_ui(&mut self, ctx: &egui::Context) {
+        if !self.closing || !self.pending_merges.is_empty() {
+            return;
+        }
+
+        egui::Window::new(self.language.sync.clone())
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                if let Some(message) = &mut self.commit_message {
+                    ui.label(self.language.commit_message.clone());
+                    ui.add(
+                        egui::TextEdit::multiline(message)
+                            .desired_width(self.settings.font_size * 30.0),
+                    );
+                    if let Some(error) = &self.sync_status.error {
+                        ui.label(egui::RichText::new(error).color(ui.visuals().error_fg_color));
+                    }
+                    ui.horizontal(|ui| {
+                        let idle = self.sync_status.running.is_none();
+                        let commit = ui.add_enabled(
+                            idle,
+                            egui::Button::new(self.language.commit_and_sync.clone()),
+                        );
+                        if commit.clicked() {
+                            // Keep the edited message until the job has really started.
+                            if let Some(message) = self.commit_message.clone() {
+                                if self.start_git_with_message(GitJob::Sync, message) {
+                                    self.commit_message = None;
+                                }
+                            }
+                        }
+                        if ui.button(self.language.cancel.clone()).clicked() {
+                            self.commit_message = None;
+                            self.closing = false;
+                        }
+                    });
+                    return;
+                }
+
+                if self.sync_status.running.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(self.language.syncing_before_closing.clone());
+                    });
+                    return;
+                }
+
+                if let Some(error) = self.sync_status.error.clone() {
+                    ui.label(
+                        egui::RichText::new(self.language.sync_failed.clone())
+                            .color(ui.visuals().error_fg_color),
+                    );
+                    ui.label(error);
+                    ui.label(self.language.changes_kept_locally.clone());
+                    ui.horizontal(|ui| {
+                        if ui.button(self.language.retry.clone()).clicked() {
+                            self.start_git(GitJob::Sync);
+                        }
+                        if ui.button(self.language.quit_anyway.clone()).clicked() {
+                            self.close_confirmed = true;
+                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                        }
+                        if ui.button(self.language.cancel.clone()).clicked() {
+                            self.closing = false;
+                        }
+                    });
+                }
+            });
+    }
+}